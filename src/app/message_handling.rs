@@ -1,89 +1,265 @@
+use iced::keyboard::Modifiers;
 use lorecore::types::*;
 
 use crate::{
     app::state::{GuiState, ViewType},
     dialog::{
-        change_role::ChangeRoleData, new_descriptor::NewDescriptorData, new_entity::NewEntityData,
-        new_history_item::NewHistoryData, new_relationship::NewRelationshipData,
-        redate_history::RedateHistoryData, relabel_entity::RelabelEntityData,
-        rename_descriptor::RenameDescriptorData, DialogUpdate,
+        change_category::ChangeCategoryData,
+        change_role::ChangeRoleData,
+        duplicate_entity::DuplicateEntityData,
+        find_replace::FindReplaceData,
+        link_mentions::LinkMentionsData,
+        merge_entities::MergeEntitiesData,
+        new_descriptor::NewDescriptorData,
+        new_entity::{NewEntityData, NewEntityDialog},
+        new_era::NewEraData,
+        new_history_item::NewHistoryData,
+        new_inverse_role_pair::NewInverseRolePairData,
+        new_relationship::NewRelationshipData,
+        redate_history::RedateHistoryData,
+        redate_history_items::RedateHistoryItemsData,
+        relabel_entity::RelabelEntityData,
+        rename_descriptor::RenameDescriptorData,
+        rename_descriptor_everywhere::RenameDescriptorEverywhereData,
+        rename_role::RenameRoleData,
+        retarget_relationship::RetargetRelationshipData,
+        DialogUpdate,
     },
     entity_view::EntityViewMessage,
     errors::LoreGuiError,
+    family_tree_view::FamilyTreeViewMessage,
     history_view::HistoryViewMessage,
+    matrix_view::MatrixViewMessage,
     relationship_view::RelationshipViewMessage,
+    table_view::TableViewMessage,
 };
 
 #[derive(Debug, Clone)]
 pub(crate) enum GuiMessage {
     ViewSelected(ViewType),
+    RefreshReport,
     NewDatabase,
     OpenDatabase,
+    FindAndReplace,
+    ReplaceMatches(FindReplaceData),
+    SuggestLinks(Option<(Label, Descriptor)>),
+    LinkMentions(LinkMentionsData),
     SetDisplayProtected(bool),
     SetEditMode(bool),
+    ModifiersChanged(Modifiers),
     EntityViewUpd(EntityViewMessage),
     HistoryViewUpd(HistoryViewMessage),
     RelationshipViewUpd(RelationshipViewMessage),
+    MatrixViewUpd(MatrixViewMessage),
+    FamilyTreeViewUpd(FamilyTreeViewMessage),
+    TableViewUpd(TableViewMessage),
     DialogUpdate(DialogUpdate),
     DialogSubmit,
     DialogClosed,
+    OpenNewEntityDialog(NewEntityData),
     NewEntity(NewEntityData),
     RelabelEntity(RelabelEntityData),
+    DuplicateEntity(DuplicateEntityData),
+    ConfirmMergeEntities(MergeEntitiesData),
+    MergeEntities(MergeEntitiesData),
     DeleteEntity(Label),
+    DeleteEntities(Vec<Label>),
+    ChangeCategory(ChangeCategoryData),
+    GoToEntity(Label),
+    GoToDescription(Label, Descriptor),
     NewDescriptor(NewDescriptorData),
     RenameDescriptor(RenameDescriptorData),
+    RenameDescriptorEverywhere(RenameDescriptorEverywhereData),
     DeleteDescriptor(Label, Descriptor),
+    DeleteDescriptors(Vec<Label>, Vec<Descriptor>),
     NewHistoryItem(NewHistoryData),
     RedateHistoryItem(RedateHistoryData),
     DeleteHistoryItem(Timestamp),
+    RedateHistoryItems(RedateHistoryItemsData),
+    DeleteHistoryItems(Vec<Timestamp>),
+    GoToHistoryItem(Year, Day, Timestamp),
+    NewEra(NewEraData),
+    DeleteEra(String),
     NewRelationship(NewRelationshipData),
     ChangeRole(ChangeRoleData),
     DeleteRelationship(EntityRelationship),
+    RetargetRelationship(RetargetRelationshipData),
+    NewInverseRolePair(NewInverseRolePairData),
+    DeleteInverseRolePair(Role),
+    RenameRole(RenameRoleData),
+    DeleteRole(Role),
+}
+
+impl GuiMessage {
+    fn changes_entities(&self) -> bool {
+        matches!(
+            self,
+            GuiMessage::ReplaceMatches(_)
+                | GuiMessage::NewEntity(_)
+                | GuiMessage::RelabelEntity(_)
+                | GuiMessage::DuplicateEntity(_)
+                | GuiMessage::MergeEntities(_)
+                | GuiMessage::DeleteEntity(_)
+                | GuiMessage::DeleteEntities(_)
+                | GuiMessage::NewDescriptor(_)
+                | GuiMessage::RenameDescriptor(_)
+                | GuiMessage::RenameDescriptorEverywhere(_)
+                | GuiMessage::DeleteDescriptor(_, _)
+                | GuiMessage::DeleteDescriptors(_, _)
+                | GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionSave)
+                | GuiMessage::TableViewUpd(TableViewMessage::CellSave)
+        )
+    }
+
+    fn changes_relationships(&self) -> bool {
+        self.changes_entities()
+            || matches!(
+                self,
+                GuiMessage::NewRelationship(_)
+                    | GuiMessage::ChangeRole(_)
+                    | GuiMessage::DeleteRelationship(_)
+                    | GuiMessage::RetargetRelationship(_)
+                    | GuiMessage::NewInverseRolePair(_)
+                    | GuiMessage::DeleteInverseRolePair(_)
+                    | GuiMessage::RenameRole(_)
+                    | GuiMessage::DeleteRole(_)
+                    | GuiMessage::MatrixViewUpd(MatrixViewMessage::CellSave)
+            )
+    }
+
+    /// History item participants count as texts, since the entity view lists them.
+    fn changes_texts(&self) -> bool {
+        self.changes_entities()
+            || matches!(
+                self,
+                GuiMessage::LinkMentions(_)
+                    | GuiMessage::ChangeCategory(_)
+                    | GuiMessage::NewHistoryItem(_)
+                    | GuiMessage::RedateHistoryItem(_)
+                    | GuiMessage::DeleteHistoryItem(_)
+                    | GuiMessage::RedateHistoryItems(_)
+                    | GuiMessage::DeleteHistoryItems(_)
+                    | GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave)
+                    | GuiMessage::HistoryViewUpd(HistoryViewMessage::AddParticipant(_))
+                    | GuiMessage::HistoryViewUpd(HistoryViewMessage::RemoveParticipant(_))
+            )
+    }
 }
 
 impl GuiState {
     pub(super) fn handle_message(&mut self, message: GuiMessage) -> Result<(), LoreGuiError> {
+        let changes_entities = message.changes_entities();
+        let changes_texts = message.changes_texts();
+        let changes_relationships = message.changes_relationships();
         match message {
-            GuiMessage::ViewSelected(view) => self.selected_view = view,
+            GuiMessage::ViewSelected(view) => self.select_view(view)?,
+            GuiMessage::RefreshReport => self.report_view_state.update(&self.lore_database)?,
             GuiMessage::NewDatabase => self.new_database_from_dialog()?,
             GuiMessage::OpenDatabase => self.open_database_from_dialog()?,
+            GuiMessage::FindAndReplace => self.open_find_replace_dialog()?,
+            GuiMessage::ReplaceMatches(data) => self.replace_matches(data)?,
+            GuiMessage::SuggestLinks(description) => self.open_link_mentions_dialog(description)?,
+            GuiMessage::LinkMentions(data) => self.link_mentions(data)?,
             GuiMessage::SetDisplayProtected(display_protected) => {
                 self.display_protected = display_protected;
                 self.entity_view_state.display_protected = display_protected;
                 self.relationship_view_state.display_protected = display_protected;
+                self.matrix_view_state.display_protected = display_protected;
+                self.family_tree_view_state.display_protected = display_protected;
+                self.table_view_state.display_protected = display_protected;
+                self.statistics_view_state.display_protected = display_protected;
             }
             GuiMessage::SetEditMode(edit_mode) => {
                 self.edit_mode = edit_mode;
                 self.entity_view_state.edit_mode = edit_mode;
                 self.history_view_state.edit_mode = edit_mode;
                 self.relationship_view_state.edit_mode = edit_mode;
+                self.matrix_view_state.edit_mode = edit_mode;
+                self.table_view_state.edit_mode = edit_mode;
+            }
+            GuiMessage::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers;
+                return Ok(());
             }
             GuiMessage::EntityViewUpd(event) => self.update_entity_view(event)?,
             GuiMessage::HistoryViewUpd(event) => self.update_history_view(event)?,
             GuiMessage::RelationshipViewUpd(event) => self.update_relationship_view(event)?,
+            GuiMessage::MatrixViewUpd(event) => self.update_matrix_view(event)?,
+            GuiMessage::FamilyTreeViewUpd(event) => self.update_family_tree_view(event)?,
+            GuiMessage::TableViewUpd(event) => self.update_table_view(event)?,
             GuiMessage::DialogUpdate(update) => self.update_dialog(update),
             GuiMessage::DialogSubmit => self.dialog_submit()?,
             GuiMessage::DialogClosed => self.dialog = None,
+            GuiMessage::OpenNewEntityDialog(data) => {
+                self.dialog = Some(Box::new(NewEntityDialog::with_data(data)))
+            }
             GuiMessage::NewEntity(data) => self.write_new_entity(data)?,
             GuiMessage::RelabelEntity(data) => self.relabel_entity(data)?,
+            GuiMessage::DuplicateEntity(data) => self.duplicate_entity(data)?,
+            GuiMessage::ConfirmMergeEntities(data) => self.confirm_merge_entities(data)?,
+            GuiMessage::MergeEntities(data) => self.merge_entities(data)?,
             GuiMessage::DeleteEntity(label) => self.delete_entity(label)?,
+            GuiMessage::DeleteEntities(labels) => self.delete_entities(labels)?,
+            GuiMessage::ChangeCategory(data) => self.change_category(data)?,
+            GuiMessage::GoToEntity(label) => self.go_to_entity(label)?,
+            GuiMessage::GoToDescription(label, descriptor) => {
+                self.go_to_description(label, descriptor)?
+            }
             GuiMessage::NewDescriptor(data) => self.write_new_descriptor(data)?,
             GuiMessage::RenameDescriptor(data) => self.change_descriptor(data)?,
+            GuiMessage::RenameDescriptorEverywhere(data) => {
+                self.change_descriptor_everywhere(data)?
+            }
             GuiMessage::DeleteDescriptor(label, descriptor) => {
                 self.delete_descriptor(label, descriptor)?
             }
+            GuiMessage::DeleteDescriptors(labels, descriptors) => {
+                self.delete_descriptors(labels, descriptors)?
+            }
             GuiMessage::NewHistoryItem(data) => self.write_new_history(data)?,
             GuiMessage::RedateHistoryItem(data) => self.redate_history_item(data)?,
             GuiMessage::DeleteHistoryItem(timestamp) => self.delete_history_item(timestamp)?,
+            GuiMessage::RedateHistoryItems(data) => self.redate_history_items(data)?,
+            GuiMessage::DeleteHistoryItems(timestamps) => self.delete_history_items(timestamps)?,
+            GuiMessage::GoToHistoryItem(year, day, timestamp) => {
+                self.go_to_history_item(year, day, timestamp)?
+            }
+            GuiMessage::NewEra(data) => self.write_new_era(data)?,
+            GuiMessage::DeleteEra(name) => self.delete_era(name)?,
             GuiMessage::NewRelationship(data) => self.write_new_relationship(data)?,
             GuiMessage::ChangeRole(data) => self.change_relationship_role(data)?,
             GuiMessage::DeleteRelationship(relationship) => {
                 self.delete_relationship(relationship)?
             }
+            GuiMessage::RetargetRelationship(data) => self.retarget_relationship(data)?,
+            GuiMessage::NewInverseRolePair(data) => self.write_new_inverse_role_pair(data)?,
+            GuiMessage::DeleteInverseRolePair(role) => self.delete_inverse_role_pair(role)?,
+            GuiMessage::RenameRole(data) => self.rename_role(data)?,
+            GuiMessage::DeleteRole(role) => self.delete_unused_role(role)?,
+        }
+        if changes_entities {
+            self.update_label_options()?;
+        }
+        if changes_texts {
+            self.entity_view_state.texts_outdated = true;
         }
         self.entity_view_state.update(&self.lore_database)?;
         self.history_view_state.update(&self.lore_database)?;
-        self.relationship_view_state.update(&self.lore_database)?;
+        if changes_relationships || self.selected_view == ViewType::Relationship {
+            self.relationship_view_state.update(&self.lore_database)?;
+        }
+        if changes_relationships || self.selected_view == ViewType::Matrix {
+            self.matrix_view_state.update(&self.lore_database)?;
+        }
+        if changes_relationships || self.selected_view == ViewType::FamilyTree {
+            self.family_tree_view_state.update(&self.lore_database)?;
+        }
+        if changes_texts || self.selected_view == ViewType::Table {
+            self.table_view_state.update(&self.lore_database)?;
+        }
+        if self.selected_view == ViewType::Statistics {
+            self.statistics_view_state.update(&self.lore_database)?;
+        }
         Ok(())
     }
 
@@ -93,6 +269,21 @@ impl GuiState {
         }
     }
 
+    /// Views that read the whole database are refreshed when they are shown.
+    pub(super) fn select_view(&mut self, view: ViewType) -> Result<(), LoreGuiError> {
+        self.selected_view = view;
+        match self.selected_view {
+            ViewType::Relationship => self.relationship_view_state.update(&self.lore_database)?,
+            ViewType::Matrix => self.matrix_view_state.update(&self.lore_database)?,
+            ViewType::FamilyTree => self.family_tree_view_state.update(&self.lore_database)?,
+            ViewType::Table => self.table_view_state.update(&self.lore_database)?,
+            ViewType::Report => self.report_view_state.update(&self.lore_database)?,
+            ViewType::Statistics => self.statistics_view_state.update(&self.lore_database)?,
+            _ => (),
+        }
+        Ok(())
+    }
+
     fn dialog_submit(&mut self) -> Result<(), LoreGuiError> {
         if let Some(dialog) = self.dialog.as_ref() {
             self.handle_message(dialog.submit())
@@ -125,6 +316,54 @@ mod tests {
         assert_eq!(gui.selected_view, ViewType::History);
     }
 
+    #[test]
+    fn report_is_computed_when_shown_or_refreshed() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.handle_message(GuiMessage::SetEditMode(true)).unwrap();
+        assert!(gui.report_view_state.issues.is_empty());
+
+        let message = GuiMessage::ViewSelected(ViewType::Report);
+        gui.handle_message(message).unwrap();
+        let issue_count = gui.report_view_state.issues.len();
+        assert!(issue_count > 0);
+
+        let nameless = EntityColumn {
+            label: "entity_without_a_name".into(),
+            descriptor: "_category".into(),
+            description: "Some Category".into(),
+        };
+        let db = gui.lore_database.as_ref().unwrap();
+        db.write_entity_columns(vec![nameless]).unwrap();
+        gui.handle_message(GuiMessage::SetEditMode(false)).unwrap();
+        assert_eq!(gui.report_view_state.issues.len(), issue_count);
+        gui.handle_message(GuiMessage::RefreshReport).unwrap();
+        assert_eq!(gui.report_view_state.issues.len(), issue_count + 1);
+    }
+
+    #[test]
+    fn statistics_are_computed_while_shown() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.handle_message(GuiMessage::SetEditMode(true)).unwrap();
+        assert_eq!(gui.statistics_view_state.statistics.entity_count, 0);
+
+        let message = GuiMessage::ViewSelected(ViewType::Statistics);
+        gui.handle_message(message).unwrap();
+        let entity_count = gui.statistics_view_state.statistics.entity_count;
+        assert!(entity_count > 0);
+        let message = GuiMessage::NewEntity(example_new_entity_data());
+        gui.handle_message(message).unwrap();
+        assert_eq!(
+            gui.statistics_view_state.statistics.entity_count,
+            entity_count + 1
+        );
+    }
+
     #[test]
     fn dialog_submit_closes_dialog() {
         let dialog = Box::new(crate::dialog::error::ErrorDialog::new(
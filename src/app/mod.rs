@@ -2,6 +2,11 @@ pub(super) mod message_handling;
 pub(crate) mod state;
 mod updating_database;
 mod updating_entity_view;
+mod updating_family_tree_view;
 mod updating_history_view;
+mod updating_matrix_view;
 mod updating_relationship_view;
+mod updating_report_view;
+mod updating_statistics_view;
+mod updating_table_view;
 mod widget;
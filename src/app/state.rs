@@ -1,3 +1,4 @@
+use iced::keyboard::Modifiers;
 use lorecore::{
     sql::lore_database::LoreDatabase,
     types::{Child, Day, Descriptor, Label, Parent, Role, Timestamp, Year},
@@ -6,8 +7,13 @@ use lorecore::{
 use crate::{
     dialog::{error::ErrorDialog, Dialog},
     entity_view::EntityViewState,
+    family_tree_view::FamilyTreeViewState,
     history_view::HistoryViewState,
+    matrix_view::MatrixViewState,
     relationship_view::RelationshipViewState,
+    report_view::ReportViewState,
+    statistics_view::StatisticsViewState,
+    table_view::TableViewState,
     user_preferences::load_database_path,
 };
 
@@ -15,9 +21,15 @@ pub(crate) struct GuiState {
     pub(super) selected_view: ViewType,
     pub(super) display_protected: bool,
     pub(super) edit_mode: bool,
+    pub(super) modifiers: Modifiers,
     pub(super) entity_view_state: EntityViewState,
     pub(super) history_view_state: HistoryViewState,
     pub(super) relationship_view_state: RelationshipViewState,
+    pub(super) matrix_view_state: MatrixViewState,
+    pub(super) family_tree_view_state: FamilyTreeViewState,
+    pub(super) table_view_state: TableViewState,
+    pub(super) report_view_state: ReportViewState,
+    pub(super) statistics_view_state: StatisticsViewState,
     pub(super) lore_database: Option<LoreDatabase>,
     pub(crate) dialog: Option<Box<dyn Dialog>>,
 }
@@ -28,6 +40,11 @@ pub(crate) enum ViewType {
     Entity,
     History,
     Relationship,
+    Matrix,
+    FamilyTree,
+    Table,
+    Report,
+    Statistics,
 }
 
 impl Default for GuiState {
@@ -36,9 +53,15 @@ impl Default for GuiState {
             selected_view: ViewType::default(),
             display_protected: false,
             edit_mode: false,
+            modifiers: Modifiers::default(),
             entity_view_state: EntityViewState::default(),
             history_view_state: HistoryViewState::default(),
             relationship_view_state: RelationshipViewState::default(),
+            matrix_view_state: MatrixViewState::default(),
+            family_tree_view_state: FamilyTreeViewState::default(),
+            table_view_state: TableViewState::default(),
+            report_view_state: ReportViewState::default(),
+            statistics_view_state: StatisticsViewState::default(),
             lore_database: None,
             dialog: None,
         };
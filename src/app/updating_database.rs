@@ -1,7 +1,17 @@
 use crate::{
-    app::state::GuiState, errors::LoreGuiError, file_dialogs, user_preferences::store_database_path,
+    app::state::GuiState,
+    dialog::{
+        find_replace::{read_text_sources, FindReplaceData, FindReplaceDialog, TextLocation},
+        label_picker::read_label_options,
+        link_mentions::{LinkMentionsData, LinkMentionsDialog},
+    },
+    errors::LoreGuiError,
+    file_dialogs,
+    report_view::ReportViewState,
+    statistics_view::statistics::Statistics,
+    user_preferences::store_database_path,
 };
-use lorecore::sql::lore_database::LoreDatabase;
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
 use std::path::PathBuf;
 
 impl GuiState {
@@ -12,6 +22,30 @@ impl GuiState {
             .reset_selections(&self.lore_database)?;
         self.relationship_view_state
             .reset_selections(&self.lore_database)?;
+        self.matrix_view_state
+            .reset_selections(&self.lore_database)?;
+        self.family_tree_view_state
+            .reset_selections(&self.lore_database)?;
+        self.table_view_state
+            .reset_selections(&self.lore_database)?;
+        self.report_view_state = ReportViewState::default();
+        self.statistics_view_state.statistics = Statistics::default();
+        self.select_view(self.selected_view.clone())?;
+        self.update_label_options()?;
+        Ok(())
+    }
+
+    pub(super) fn update_label_options(&mut self) -> Result<(), LoreGuiError> {
+        let label_options = match &self.lore_database {
+            Some(db) => read_label_options(db)?,
+            None => vec![],
+        };
+        self.entity_view_state
+            .current_description
+            .set_label_options(label_options.clone());
+        self.history_view_state
+            .current_content
+            .set_label_options(label_options);
         Ok(())
     }
 
@@ -46,4 +80,53 @@ impl GuiState {
         self.update_database_derived_data()?;
         Ok(())
     }
+
+    pub(super) fn open_find_replace_dialog(&mut self) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let data = FindReplaceData::new(read_text_sources(db)?);
+        self.dialog = Some(Box::new(FindReplaceDialog::new(data)));
+        Ok(())
+    }
+
+    pub(super) fn replace_matches(&mut self, data: FindReplaceData) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        data.write_to_database(db)?;
+        Ok(())
+    }
+
+    /// Without a description, all texts are searched.
+    pub(super) fn open_link_mentions_dialog(
+        &mut self,
+        description: Option<(Label, Descriptor)>,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let mut sources = read_text_sources(db)?;
+        if let Some((label, descriptor)) = description {
+            sources.retain(|source| match &source.location {
+                TextLocation::Description(l, d) => l == &label && d == &descriptor,
+                TextLocation::HistoryContent(_, _, _) => false,
+            });
+        }
+        let data = LinkMentionsData::new(sources, &read_label_options(db)?);
+        self.dialog = Some(Box::new(LinkMentionsDialog::new(data)));
+        Ok(())
+    }
+
+    pub(super) fn link_mentions(&mut self, data: LinkMentionsData) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        data.write_to_database(db)?;
+        Ok(())
+    }
 }
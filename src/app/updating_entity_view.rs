@@ -1,14 +1,28 @@
-use lorecore::{sql::lore_database::LoreDatabase, types::*};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, RelationshipSearchParams, SqlSearchText},
+    },
+    types::*,
+};
 
 use crate::{
-    app::state::GuiState,
+    app::state::{GuiState, ViewType},
+    csv,
     db_col_view::{entry::DbColViewEntry, ColViewMes},
     dialog::{
+        change_category::{ChangeCategoryData, ChangeCategoryDialog},
         confirmation::ConfirmationDialog,
+        duplicate_entity::{DuplicateEntityData, DuplicateEntityDialog},
+        label_picker::read_label_options,
+        merge_entities::{MergeEntitiesData, MergeEntitiesDialog},
         new_descriptor::{NewDescriptorData, NewDescriptorDialog},
         new_entity::{NewEntityData, NewEntityDialog},
         relabel_entity::{RelabelEntityData, RelabelEntityDialog},
         rename_descriptor::{RenameDescriptorData, RenameDescriptorDialog},
+        rename_descriptor_everywhere::{
+            RenameDescriptorEverywhereData, RenameDescriptorEverywhereDialog,
+        },
     },
     entity_view::{EntityViewMessage, EntityViewState},
     errors::LoreGuiError,
@@ -26,17 +40,68 @@ impl GuiState {
             EntityViewMessage::RelabelEntity(data) => {
                 self.dialog = Some(Box::new(RelabelEntityDialog::new(data)))
             }
+            EntityViewMessage::DuplicateEntity(data) => {
+                self.dialog = Some(Box::new(DuplicateEntityDialog::new(data)))
+            }
+            EntityViewMessage::MergeEntity(label) => {
+                let db = self
+                    .lore_database
+                    .as_ref()
+                    .ok_or(LoreGuiError::NoDatabase)?;
+                let mut labels = read_label_options(db)?;
+                labels.retain(|option| option.label != label);
+                let columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+                let data = MergeEntitiesData::new(label);
+                self.dialog = Some(Box::new(MergeEntitiesDialog::new(data, labels, columns)))
+            }
             EntityViewMessage::DeleteEntity(label) => {
-                let message = format!("Do you really want to delete {}?", label);
+                let mut message = format!("Do you really want to delete {}?", label);
+                let relationship_count = self.count_relationships_of(&label)?;
+                if relationship_count > 0 {
+                    message = format!(
+                        "{} It is part of {} relationships. Any left behind are listed as dangling in the relationship view.",
+                        message, relationship_count
+                    );
+                }
                 let on_confirm = GuiMessage::DeleteEntity(label);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
             }
+            EntityViewMessage::DeleteEntities(labels) => {
+                let mut relationship_count = 0;
+                for label in labels.iter() {
+                    relationship_count += self.count_relationships_of(label)?;
+                }
+                let message = format!(
+                    "Do you really want to delete these {} entities: {}? Together they are part of {} relationships.",
+                    labels.len(),
+                    join_labels(&labels),
+                    relationship_count
+                );
+                let on_confirm = GuiMessage::DeleteEntities(labels);
+                self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
+            }
+            EntityViewMessage::ChangeCategory(labels) => {
+                let data = ChangeCategoryData::new(labels);
+                self.dialog = Some(Box::new(ChangeCategoryDialog::new(data)))
+            }
+            EntityViewMessage::ExportEntities(labels) => self.export_entities(&labels)?,
             EntityViewMessage::NewDescriptor(label) => {
                 self.dialog = Some(Box::new(NewDescriptorDialog::new(label.clone())))
             }
             EntityViewMessage::RenameDescriptor(data) => {
                 self.dialog = Some(Box::new(RenameDescriptorDialog::new(data)))
             }
+            EntityViewMessage::RenameDescriptorEverywhere(descriptor) => {
+                let db = self
+                    .lore_database
+                    .as_ref()
+                    .ok_or(LoreGuiError::NoDatabase)?;
+                let columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+                let data = RenameDescriptorEverywhereData::new(descriptor);
+                self.dialog = Some(Box::new(RenameDescriptorEverywhereDialog::new(
+                    data, columns,
+                )))
+            }
             EntityViewMessage::DeleteDescriptor(label, descriptor) => {
                 let message = format!(
                     "Do you really want to delete {}'s descriptor {}?",
@@ -45,11 +110,32 @@ impl GuiState {
                 let on_confirm = GuiMessage::DeleteDescriptor(label, descriptor);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
             }
+            EntityViewMessage::DeleteDescriptors(labels, descriptors) => {
+                let descriptor_list = descriptors
+                    .iter()
+                    .map(|d| d.to_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let message = format!(
+                    "Do you really want to delete the descriptors {} from the {} entities {}?",
+                    descriptor_list,
+                    labels.len(),
+                    join_labels(&labels)
+                );
+                let on_confirm = GuiMessage::DeleteDescriptors(labels, descriptors);
+                self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
+            }
+            EntityViewMessage::NewRelationship(label) => {
+                self.open_new_relationship_dialog(Some(label.to_str().into()))?
+            }
             EntityViewMessage::LabelViewUpdate(event) => self.update_label_view(event)?,
             EntityViewMessage::DescriptorViewUpdate(event) => self.update_descriptor_view(event)?,
             EntityViewMessage::DescriptionUpdate(action) => {
                 self.entity_view_state.current_description.perform(action)
             }
+            EntityViewMessage::DescriptionComplete(label) => {
+                self.entity_view_state.current_description.complete(&label)
+            }
             EntityViewMessage::DescriptionDiscard => {
                 self.entity_view_state.current_description.reset()
             }
@@ -70,6 +156,11 @@ impl GuiState {
                 db.change_entity_description((&label, &descriptor), &description)?;
                 self.entity_view_state.current_description.saved();
             }
+            EntityViewMessage::ShowOverview(show) => self.entity_view_state.show_overview = show,
+            EntityViewMessage::EditDescriptor(descriptor) => {
+                self.entity_view_state.show_overview = false;
+                self.set_selected_descriptor(Some(descriptor));
+            }
         };
         self.entity_view_state.update(&self.lore_database)?;
         Ok(())
@@ -84,8 +175,8 @@ impl GuiState {
             ColViewMes::SearchFieldUpd(text) => {
                 state.label_view_state.set_search_text(text);
             }
-            ColViewMes::Selected(_index, label) => {
-                state.set_selected_label(label.0);
+            ColViewMes::Selected(index, label) => {
+                state.label_view_state.select(index, label, self.modifiers);
                 state.set_selected_descriptor(None);
             }
         };
@@ -102,8 +193,10 @@ impl GuiState {
             ColViewMes::SearchFieldUpd(text) => {
                 state.descriptor_view_state.set_search_text(text);
             }
-            ColViewMes::Selected(_index, descriptor) => {
-                state.set_selected_descriptor(descriptor.0);
+            ColViewMes::Selected(index, descriptor) => {
+                state
+                    .descriptor_view_state
+                    .select(index, descriptor, self.modifiers);
             }
         };
 
@@ -111,6 +204,34 @@ impl GuiState {
         Ok(())
     }
 
+    pub(super) fn go_to_entity(&mut self, label: Label) -> Result<(), LoreGuiError> {
+        let state = &mut self.entity_view_state;
+        if state.label_view_state.get_search_text().is_some() {
+            // Asks the database, so that the label list decides what the search text matches.
+            if !state
+                .get_current_labels(&self.lore_database)?
+                .contains(&label)
+            {
+                state.label_view_state.set_search_text(String::new());
+            }
+        }
+        self.set_selected_label(Some(label));
+        self.set_selected_descriptor(None);
+        self.selected_view = ViewType::Entity;
+        Ok(())
+    }
+
+    pub(super) fn go_to_description(
+        &mut self,
+        label: Label,
+        descriptor: Descriptor,
+    ) -> Result<(), LoreGuiError> {
+        self.go_to_entity(label)?;
+        self.entity_view_state.show_overview = false;
+        self.set_selected_descriptor(Some(descriptor));
+        Ok(())
+    }
+
     pub(super) fn write_new_entity(&mut self, data: NewEntityData) -> Result<(), LoreGuiError> {
         let db = self
             .lore_database
@@ -133,6 +254,65 @@ impl GuiState {
         Ok(())
     }
 
+    pub(super) fn duplicate_entity(
+        &mut self,
+        data: DuplicateEntityData,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let new_label = data.new_label().clone();
+        data.write_to_database(db)?;
+        self.set_selected_label(Some(new_label));
+        self.set_selected_descriptor(None);
+        Ok(())
+    }
+
+    pub(super) fn confirm_merge_entities(
+        &mut self,
+        data: MergeEntitiesData,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let message = data.summary(db)?;
+        let on_confirm = GuiMessage::MergeEntities(data);
+        self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)));
+        Ok(())
+    }
+
+    pub(super) fn merge_entities(&mut self, data: MergeEntitiesData) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let survivor = data.survivor().clone();
+        data.write_to_database(db)?;
+        self.set_selected_label(Some(survivor));
+        self.set_selected_descriptor(None);
+        Ok(())
+    }
+
+    fn count_relationships_of(&self, label: &Label) -> Result<usize, LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let as_parent =
+            RelationshipSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+        let as_child =
+            RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label.to_str())));
+        // Relationships of the entity with itself are found both ways, but counted once.
+        let incoming = db
+            .read_relationships(as_child)?
+            .into_iter()
+            .filter(|rel| rel.parent.to_str() != label.to_str())
+            .count();
+        Ok(db.read_relationships(as_parent)?.len() + incoming)
+    }
+
     pub(super) fn delete_entity(&mut self, label: Label) -> Result<(), LoreGuiError> {
         let db = self
             .lore_database
@@ -143,6 +323,52 @@ impl GuiState {
         Ok(())
     }
 
+    pub(super) fn delete_entities(&mut self, labels: Vec<Label>) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        for label in labels {
+            db.delete_entity(label)?;
+        }
+        self.set_selected_label(None);
+        Ok(())
+    }
+
+    pub(super) fn change_category(&mut self, data: ChangeCategoryData) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        data.write_to_database(db)?;
+        Ok(())
+    }
+
+    fn export_entities(&self, labels: &[Label]) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let header = vec![
+            "label".to_string(),
+            "descriptor".to_string(),
+            "description".to_string(),
+        ];
+        let mut rows = vec![header];
+        for label in labels {
+            let search_params =
+                EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+            for col in db.read_entity_columns(search_params)? {
+                rows.push(vec![
+                    col.label.to_str().to_string(),
+                    col.descriptor.to_str().to_string(),
+                    col.description.to_str().to_string(),
+                ]);
+            }
+        }
+        csv::export("entities.csv", &rows)
+    }
+
     pub(super) fn write_new_descriptor(
         &mut self,
         data: NewDescriptorData,
@@ -175,6 +401,20 @@ impl GuiState {
         Ok(())
     }
 
+    pub(super) fn change_descriptor_everywhere(
+        &mut self,
+        data: RenameDescriptorEverywhereData,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let descriptor = data.new_descriptor().clone();
+        data.update_descriptors_in_database(db)?;
+        self.set_selected_descriptor(Some(descriptor));
+        Ok(())
+    }
+
     pub(super) fn delete_descriptor(
         &mut self,
         label: Label,
@@ -189,6 +429,38 @@ impl GuiState {
         self.set_selected_descriptor(None);
         Ok(())
     }
+
+    pub(super) fn delete_descriptors(
+        &mut self,
+        labels: Vec<Label>,
+        descriptors: Vec<Descriptor>,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        for label in labels.iter() {
+            for descriptor in descriptors.iter() {
+                let search_params = EntityColumnSearchParams::new(
+                    Some(SqlSearchText::exact(label.to_str())),
+                    Some(SqlSearchText::exact(descriptor.to_str())),
+                );
+                if !db.read_entity_columns(search_params)?.is_empty() {
+                    db.delete_entity_column((label.clone(), descriptor.clone()))?;
+                }
+            }
+        }
+        self.set_selected_descriptor(None);
+        Ok(())
+    }
+}
+
+fn join_labels(labels: &[Label]) -> String {
+    labels
+        .iter()
+        .map(|label| label.to_str())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl EntityViewState {
@@ -199,6 +471,7 @@ impl EntityViewState {
         self.set_selected_label(None);
         self.set_selected_descriptor(None);
         self.set_description_text("");
+        self.texts_outdated = true;
         self.update(db)?;
         Ok(())
     }
@@ -207,6 +480,9 @@ impl EntityViewState {
         self.update_labels(db)?;
         self.update_descriptors(db)?;
         self.update_description(db)?;
+        self.update_texts_of_label(db)?;
+        self.update_relationships(db)?;
+        self.update_overview(db)?;
         Ok(())
     }
 
@@ -232,7 +508,39 @@ impl EntityViewState {
 
     fn update_description(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
         let description = self.get_current_description(db)?;
-        self.set_description_text(description.to_str());
+        let source = format!(
+            "{:?}",
+            (self.get_selected_label(), self.get_selected_descriptor())
+        );
+        self.current_description
+            .update_persisted(source, description.to_str());
+        Ok(())
+    }
+
+    /// Searching all texts is only done for a newly selected label or after a write.
+    fn update_texts_of_label(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        let label = self.get_selected_label();
+        if self.texts_outdated || self.texts_label != label {
+            self.history_items = self.get_current_history_items(db)?;
+            self.backlinks = self.get_current_backlinks(db)?;
+            self.texts_label = label;
+            self.texts_outdated = false;
+        }
+        Ok(())
+    }
+
+    fn update_relationships(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        (self.outgoing_relationships, self.incoming_relationships) =
+            self.get_current_relationships(db)?;
+        Ok(())
+    }
+
+    fn update_overview(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.overview = if self.show_overview {
+            self.get_current_overview(db)?
+        } else {
+            vec![]
+        };
         Ok(())
     }
 }
@@ -241,8 +549,37 @@ impl EntityViewState {
 mod tests {
     use super::*;
 
+    use crate::editor::autocomplete::reference;
     use crate::tests::{example_database, example_descriptors, example_labels};
 
+    #[test]
+    fn backlinks_are_searched_anew_only_for_another_label_or_after_a_write() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let descriptors = example_descriptors();
+        let event = ColViewMes::Selected(0, DbColViewEntry(Some(labels[0].clone())));
+        gui.update_label_view(event).unwrap();
+        gui.handle_message(GuiMessage::SetEditMode(true)).unwrap();
+        let backlink_count = gui.entity_view_state.backlinks.len();
+
+        let mention = EntityColumn {
+            label: labels[1].clone(),
+            descriptor: "Mention".into(),
+            description: format!("Meets {}.", reference(&labels[0])).into(),
+        };
+        let db = gui.lore_database.as_ref().unwrap();
+        db.write_entity_columns(vec![mention]).unwrap();
+        gui.handle_message(GuiMessage::SetEditMode(false)).unwrap();
+        assert_eq!(gui.entity_view_state.backlinks.len(), backlink_count);
+
+        let message = GuiMessage::DeleteDescriptor(labels[0].clone(), descriptors[2].0.clone());
+        gui.handle_message(message).unwrap();
+        assert_eq!(gui.entity_view_state.backlinks.len(), backlink_count + 1);
+    }
+
     #[test]
     fn selecting_label_deselects_descriptor() {
         let mut gui = GuiState {
@@ -284,4 +621,72 @@ mod tests {
         assert_eq!(gui.get_selected_descriptor(), Some(new_descriptor));
         assert_eq!(gui.get_description_text(), descriptors[1].1.to_str());
     }
+
+    #[test]
+    fn editing_descriptor_from_overview_opens_it_in_the_editor() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let descriptors = example_descriptors();
+        gui.set_selected_label(Some(labels[0].clone()));
+
+        gui.update_entity_view(EntityViewMessage::ShowOverview(true))
+            .unwrap();
+        let overview = &gui.entity_view_state.overview;
+        assert_eq!(overview.len(), descriptors.len());
+        assert!(descriptors.iter().all(|d| overview.contains(d)));
+
+        let descriptor = descriptors[2].0.clone();
+        gui.update_entity_view(EntityViewMessage::EditDescriptor(descriptor.clone()))
+            .unwrap();
+        assert!(!gui.entity_view_state.show_overview);
+        assert!(gui.entity_view_state.overview.is_empty());
+        assert_eq!(gui.get_selected_descriptor(), Some(descriptor));
+        assert_eq!(gui.get_description_text(), descriptors[2].1.to_str());
+    }
+
+    #[test]
+    fn going_to_entity_lists_its_relationships() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        gui.handle_message(GuiMessage::GoToEntity(labels[0].clone()))
+            .unwrap();
+
+        assert_eq!(gui.get_selected_label(), Some(labels[0].clone()));
+        let state = &gui.entity_view_state;
+        assert_eq!(state.outgoing_relationships.len(), labels.len() - 1);
+        assert_eq!(state.incoming_relationships.len(), labels.len() - 1);
+        assert!(state
+            .outgoing_relationships
+            .iter()
+            .all(|rel| rel.parent.to_str() == labels[0].to_str()));
+    }
+
+    #[test]
+    fn going_to_entity_keeps_a_search_that_matches_it_in_other_case() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let state = &mut gui.entity_view_state;
+        state
+            .label_view_state
+            .set_search_text("LABEL_1".to_string());
+        gui.handle_message(GuiMessage::GoToEntity(labels[0].clone()))
+            .unwrap();
+        let search_text = gui.entity_view_state.label_view_state.get_search_text();
+        assert_eq!(search_text, Some("LABEL_1"));
+
+        gui.handle_message(GuiMessage::GoToEntity(labels[1].clone()))
+            .unwrap();
+        let search_text = gui.entity_view_state.label_view_state.get_search_text();
+        assert_eq!(search_text, None);
+        assert_eq!(gui.get_selected_label(), Some(labels[1].clone()));
+    }
 }
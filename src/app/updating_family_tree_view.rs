@@ -0,0 +1,92 @@
+use lorecore::{sql::lore_database::LoreDatabase, types::Label};
+
+use crate::{
+    app::state::GuiState,
+    errors::LoreGuiError,
+    family_tree_view::{layout::layout, FamilyTreeViewMessage, FamilyTreeViewState},
+};
+
+impl GuiState {
+    pub(super) fn update_family_tree_view(
+        &mut self,
+        event: FamilyTreeViewMessage,
+    ) -> Result<(), LoreGuiError> {
+        let state = &mut self.family_tree_view_state;
+        match event {
+            FamilyTreeViewMessage::ParentRoleToggled(role, checked) => {
+                state.parent_roles.retain(|r| r != &role);
+                if checked {
+                    state.parent_roles.push(role);
+                }
+            }
+            FamilyTreeViewMessage::PartnerRoleToggled(role, checked) => {
+                state.partner_roles.retain(|r| r != &role);
+                if checked {
+                    state.partner_roles.push(role);
+                }
+            }
+            FamilyTreeViewMessage::EntitySelected(label) => state.selected_label = Some(label),
+        };
+        self.family_tree_view_state.update(&self.lore_database)?;
+        Ok(())
+    }
+}
+
+impl FamilyTreeViewState {
+    pub(super) fn reset_selections(
+        &mut self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<(), LoreGuiError> {
+        self.parent_roles = vec![];
+        self.partner_roles = vec![];
+        self.selected_label = None;
+        self.update(db)?;
+        Ok(())
+    }
+
+    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        let relationships = self.get_current_relationships(db)?;
+        self.role_options = Self::get_role_options(&relationships);
+        self.layout = layout(&relationships, &self.parent_roles, &self.partner_roles);
+        let is_in_tree = |label: &Label| self.layout.nodes.iter().any(|node| &node.label == label);
+        if !self.selected_label.as_ref().is_some_and(is_in_tree) {
+            self.selected_label = None;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_labels, example_role};
+
+    #[test]
+    fn selected_entity_is_deselected_when_it_leaves_the_tree() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let role = example_role(&labels[0].to_str().into(), &labels[1].to_str().into());
+
+        let toggle = FamilyTreeViewMessage::ParentRoleToggled(role.clone(), true);
+        gui.update_family_tree_view(toggle).unwrap();
+        let state = &gui.family_tree_view_state;
+        assert_eq!(state.layout.nodes.len(), 2);
+        assert_eq!(state.layout.descents.len(), 1);
+
+        let select = FamilyTreeViewMessage::EntitySelected(labels[1].clone());
+        gui.update_family_tree_view(select).unwrap();
+        assert_eq!(
+            gui.family_tree_view_state.selected_label,
+            Some(labels[1].clone())
+        );
+
+        let toggle = FamilyTreeViewMessage::ParentRoleToggled(role, false);
+        gui.update_family_tree_view(toggle).unwrap();
+        assert!(gui.family_tree_view_state.layout.nodes.is_empty());
+        assert_eq!(gui.family_tree_view_state.selected_label, None);
+    }
+}
@@ -1,16 +1,26 @@
+use iced::widget::combo_box;
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
 use crate::{
-    app::state::GuiState,
+    app::state::{GuiState, ViewType},
     db_col_view::{entry::DbColViewEntry, ColViewMes},
     dialog::{
         confirmation::ConfirmationDialog,
+        new_era::{NewEraData, NewEraDialog},
         new_history_item::{NewHistoryData, NewHistoryDialog},
         redate_history::{RedateHistoryData, RedateHistoryDialog},
+        redate_history_items::{RedateHistoryItemsData, RedateHistoryItemsDialog},
     },
     editor::EditorState,
     errors::LoreGuiError,
-    history_view::{HistoryViewMessage, HistoryViewState},
+    history_view::{
+        era::{era_key, read_eras, Era},
+        properties::{
+            change_history_item_properties, get_dating, get_participants, read_history_item,
+            with_participants,
+        },
+        HistoryViewMessage, HistoryViewState,
+    },
 };
 
 use super::message_handling::GuiMessage;
@@ -32,6 +42,17 @@ impl GuiState {
                 let on_confirm = GuiMessage::DeleteHistoryItem(timestamp);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
             }
+            HistoryViewMessage::RedateHistoryItems(data) => {
+                self.dialog = Some(Box::new(RedateHistoryItemsDialog::new(data)))
+            }
+            HistoryViewMessage::DeleteHistoryItems(timestamps) => {
+                let message = format!(
+                    "Do you really want to delete these {} history items?",
+                    timestamps.len()
+                );
+                let on_confirm = GuiMessage::DeleteHistoryItems(timestamps);
+                self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
+            }
             HistoryViewMessage::YearViewUpdate(event) => self.update_year_view(event)?,
             HistoryViewMessage::DayViewUpdate(event) => self.update_day_view(event)?,
             HistoryViewMessage::HistoryTimestampViewUpdate(event) => {
@@ -40,6 +61,9 @@ impl GuiState {
             HistoryViewMessage::ContentUpdate(action) => {
                 self.history_view_state.current_content.perform(action)
             }
+            HistoryViewMessage::ContentComplete(label) => {
+                self.history_view_state.current_content.complete(&label)
+            }
             HistoryViewMessage::ContentDiscard => self.history_view_state.current_content.reset(),
             HistoryViewMessage::ContentSave => {
                 let db = self
@@ -54,6 +78,23 @@ impl GuiState {
                 db.change_history_item_content(timestamp, &content)?;
                 self.history_view_state.current_content.saved();
             }
+            HistoryViewMessage::NewEra => self.dialog = Some(Box::new(NewEraDialog::new())),
+            HistoryViewMessage::DeleteEra(name) => {
+                let message = format!("Do you really want to delete the era {}?", name);
+                let on_confirm = GuiMessage::DeleteEra(name);
+                self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
+            }
+            HistoryViewMessage::EraFilterSelected(era) => self.set_era_filter(era)?,
+            HistoryViewMessage::AddParticipant(label) => {
+                let mut participants = self.history_view_state.participants.clone();
+                participants.push(label);
+                self.change_participants(&participants)?;
+            }
+            HistoryViewMessage::RemoveParticipant(label) => {
+                let mut participants = self.history_view_state.participants.clone();
+                participants.retain(|p| p != &label);
+                self.change_participants(&participants)?;
+            }
         };
         self.history_view_state.update(&self.lore_database)?;
         Ok(())
@@ -99,8 +140,10 @@ impl GuiState {
             ColViewMes::SearchFieldUpd(text) => {
                 state.timestamp_view_state.set_search_text(text);
             }
-            ColViewMes::Selected(_index, timestamp) => {
-                state.set_selected_timestamp(timestamp.0);
+            ColViewMes::Selected(index, timestamp) => {
+                state
+                    .timestamp_view_state
+                    .select(index, timestamp, self.modifiers);
             }
         };
         self.history_view_state.update(&self.lore_database)?;
@@ -140,6 +183,40 @@ impl GuiState {
         Ok(())
     }
 
+    /// Selects the new date, so that the moved items are listed.
+    pub(super) fn redate_history_items(
+        &mut self,
+        data: RedateHistoryItemsData,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let year = data.new_year();
+        let day = data.new_day();
+        data.update_dates_in_database(db)?;
+        self.set_selected_year(Some(year));
+        self.set_selected_day(day);
+        self.set_selected_timestamp(None);
+        Ok(())
+    }
+
+    pub(super) fn delete_history_items(
+        &mut self,
+        timestamps: Vec<Timestamp>,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        for timestamp in timestamps {
+            db.delete_history_item(timestamp)?;
+        }
+        self.set_selected_timestamp(None);
+        self.set_history_text("");
+        Ok(())
+    }
+
     pub(super) fn delete_history_item(&mut self, timestamp: Timestamp) -> Result<(), LoreGuiError> {
         let db = self
             .lore_database
@@ -152,6 +229,80 @@ impl GuiState {
         self.set_history_text("");
         Ok(())
     }
+
+    fn change_participants(&mut self, participants: &[Label]) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let timestamp = match self.get_selected_timestamp() {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+        let item = match read_history_item(db, timestamp)? {
+            Some(item) => item,
+            None => return Ok(()),
+        };
+        let properties = with_participants(&item.properties, participants);
+        change_history_item_properties(db, timestamp, properties)?;
+        Ok(())
+    }
+
+    pub(super) fn go_to_history_item(
+        &mut self,
+        year: Year,
+        day: Day,
+        timestamp: Timestamp,
+    ) -> Result<(), LoreGuiError> {
+        let state = &mut self.history_view_state;
+        if state.era_filter.is_some()
+            && !state
+                .get_current_years(&self.lore_database)?
+                .contains(&year)
+        {
+            state.era_filter = None;
+        }
+        self.set_selected_year(Some(year));
+        self.set_selected_day(Some(day));
+        self.set_selected_timestamp(Some(timestamp));
+        self.selected_view = ViewType::History;
+        Ok(())
+    }
+
+    /// Filters the years by an era, keeping the selection if its year still is listed.
+    pub(super) fn set_era_filter(&mut self, era: Option<Era>) -> Result<(), LoreGuiError> {
+        let state = &mut self.history_view_state;
+        state.era_filter = era;
+        if let Some(year) = state.get_selected_year() {
+            if !state
+                .get_current_years(&self.lore_database)?
+                .contains(&year)
+            {
+                state.set_selected_year(None);
+                state.set_selected_day(None);
+                state.set_selected_timestamp(None);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn write_new_era(&mut self, data: NewEraData) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        data.write_to_database(db)?;
+        Ok(())
+    }
+
+    pub(super) fn delete_era(&mut self, name: String) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        db.delete_entity_column(era_key(&name))?;
+        Ok(())
+    }
 }
 
 impl HistoryViewState {
@@ -163,15 +314,32 @@ impl HistoryViewState {
         self.day_view_state.set_selected(DbColViewEntry::NONE);
         self.timestamp_view_state.set_selected(DbColViewEntry::NONE);
         self.current_content = EditorState::default();
+        self.era_filter = None;
+        self.update_eras(db)?;
         self.update_years(db)?;
         Ok(())
     }
 
     pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.update_eras(db)?;
         self.update_years(db)?;
         self.update_days(db)?;
         self.update_timestamps(db)?;
         self.update_content(db)?;
+        self.update_properties(db)?;
+        Ok(())
+    }
+
+    fn update_eras(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.eras = match db {
+            Some(db) => read_eras(db)?,
+            None => vec![],
+        };
+        if let Some(filter) = &self.era_filter {
+            if !self.eras.contains(filter) {
+                self.era_filter = None;
+            }
+        }
         Ok(())
     }
 
@@ -205,9 +373,22 @@ impl HistoryViewState {
         Ok(())
     }
 
+    fn update_properties(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        let properties = self.get_current_properties(db)?;
+        self.participants = get_participants(&properties);
+        self.dating = get_dating(&properties);
+        let options = self.get_participant_options();
+        if self.participant_picker.options() != options.as_slice() {
+            self.participant_picker = combo_box::State::new(options);
+        }
+        Ok(())
+    }
+
     fn update_content(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
         let content = self.get_current_content(db)?;
-        self.current_content = EditorState::new(content.to_str());
+        let source = format!("{:?}", self.timestamp_view_state.get_selected());
+        self.current_content
+            .update_persisted(source, content.to_str());
         Ok(())
     }
 }
@@ -218,7 +399,14 @@ mod test {
 
     use super::*;
 
-    use crate::tests::{example_database, example_days, example_history_content, example_years};
+    use crate::dialog::new_era::tests::example_new_era_data;
+    use crate::dialog::new_history_item::tests::{
+        example_new_history_data, example_ranged_history_data,
+    };
+    use crate::history_view::{dating::Dating, properties::with_dating};
+    use crate::tests::{
+        example_database, example_days, example_history_content, example_labels, example_years,
+    };
 
     #[test]
     fn selecting_year_deselects_day_and_timestamp() {
@@ -243,4 +431,180 @@ mod test {
         assert_eq!(gui.get_selected_timestamp(), None);
         assert!(gui.get_history_text().trim().is_empty());
     }
+
+    #[test]
+    fn shift_selected_history_items_are_redated_together() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let year = example_years()[2];
+        gui.set_selected_year(Some(year));
+        gui.history_view_state.update(&gui.lore_database).unwrap();
+        let entries = gui.history_view_state.timestamp_view_state.get_entries();
+        let (first, last) = (entries[0].clone(), entries[entries.len() - 1].clone());
+        let last_index = entries.len() - 1;
+
+        gui.update_timestamp_view(ColViewMes::Selected(0, first))
+            .unwrap();
+        gui.modifiers = iced::keyboard::Modifiers::SHIFT;
+        gui.update_timestamp_view(ColViewMes::Selected(last_index, last))
+            .unwrap();
+        let timestamps = gui
+            .history_view_state
+            .timestamp_view_state
+            .get_multi_selected();
+        assert_eq!(timestamps.len(), example_days().len());
+
+        let mut data = RedateHistoryItemsData::new(timestamps.clone(), year, None);
+        let new_year: Year = (year.to_int() + 1).into();
+        data.set_new_year(new_year);
+        gui.handle_message(GuiMessage::RedateHistoryItems(data))
+            .unwrap();
+
+        assert_eq!(gui.get_selected_year(), Some(new_year));
+        let entries = gui.history_view_state.timestamp_view_state.get_entries();
+        assert!(timestamps
+            .iter()
+            .all(|t| entries.contains(&DbColViewEntry(Some(*t)))));
+        let db = gui.lore_database.as_ref().unwrap();
+        let days: Vec<Day> = timestamps
+            .iter()
+            .map(|t| read_history_item(db, *t).unwrap().unwrap().day)
+            .collect();
+        assert!(example_days().iter().all(|day| days.contains(day)));
+
+        let ranged = with_dating(
+            &read_history_item(db, timestamps[0])
+                .unwrap()
+                .unwrap()
+                .properties,
+            &Dating {
+                range_end: Some((new_year.to_int() + 10).into()),
+                ..Default::default()
+            },
+        );
+        change_history_item_properties(db, timestamps[0], ranged).unwrap();
+        let mut data = RedateHistoryItemsData::new(timestamps.clone(), new_year, None);
+        data.set_new_year((new_year.to_int() + 20).into());
+        assert!(gui
+            .handle_message(GuiMessage::RedateHistoryItems(data))
+            .is_err());
+        let db = gui.lore_database.as_ref().unwrap();
+        assert!(timestamps
+            .iter()
+            .all(|t| read_history_item(db, *t).unwrap().unwrap().year == new_year));
+    }
+
+    #[test]
+    fn selecting_era_filters_years() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let data = example_new_era_data();
+        let name = data.name().to_string();
+        gui.handle_message(GuiMessage::NewEra(data)).unwrap();
+        let era = gui
+            .history_view_state
+            .eras
+            .iter()
+            .find(|era| era.name == name)
+            .cloned();
+        assert!(era.is_some());
+
+        let event = HistoryViewMessage::EraFilterSelected(era);
+        gui.update_history_view(event).unwrap();
+
+        let years = gui
+            .history_view_state
+            .get_current_years(&gui.lore_database)
+            .unwrap();
+        assert_eq!(years, vec![(-22).into(), 0.into()]);
+    }
+
+    #[test]
+    fn adding_participant_lists_history_item_for_entity() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let data = example_new_history_data();
+        let timestamp = *data.timestamp();
+        gui.handle_message(GuiMessage::NewHistoryItem(data))
+            .unwrap();
+        gui.update_label_options().unwrap();
+        let label = example_labels()[0].clone();
+
+        let event = HistoryViewMessage::AddParticipant(label.clone());
+        gui.update_history_view(event).unwrap();
+        assert_eq!(gui.history_view_state.participants, vec![label.clone()]);
+        let options = gui.history_view_state.participant_picker.options();
+        assert!(!options.contains(&label));
+        assert!(options.contains(&example_labels()[1]));
+        assert_eq!(gui.get_selected_timestamp(), Some(timestamp));
+
+        gui.set_selected_label(Some(label));
+        gui.entity_view_state.update(&gui.lore_database).unwrap();
+        let timestamps = gui
+            .entity_view_state
+            .history_items
+            .iter()
+            .map(|item| item.timestamp)
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![timestamp]);
+    }
+
+    #[test]
+    fn year_search_finds_items_whose_range_contains_it() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let ranged_data = example_ranged_history_data();
+        let year = *ranged_data.year();
+        gui.handle_message(GuiMessage::NewHistoryItem(ranged_data))
+            .unwrap();
+
+        let search_year = year.to_int() + 5;
+        gui.history_view_state
+            .year_view_state
+            .set_search_text(search_year.to_string());
+        let years = gui
+            .history_view_state
+            .get_current_years(&gui.lore_database)
+            .unwrap();
+        assert_eq!(years, vec![year]);
+    }
+
+    #[test]
+    fn era_filter_includes_items_whose_range_overlaps_it() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let era_data = example_new_era_data();
+        let name = era_data.name().to_string();
+        gui.handle_message(GuiMessage::NewEra(era_data)).unwrap();
+        let ranged_data = example_ranged_history_data();
+        let year = *ranged_data.year();
+        gui.handle_message(GuiMessage::NewHistoryItem(ranged_data))
+            .unwrap();
+        let era = gui
+            .history_view_state
+            .eras
+            .iter()
+            .find(|era| era.name == name)
+            .cloned();
+
+        gui.set_selected_year(Some(year));
+        gui.set_era_filter(era).unwrap();
+        assert_eq!(gui.get_selected_year(), Some(year));
+
+        let years = gui
+            .history_view_state
+            .get_current_years(&gui.lore_database)
+            .unwrap();
+        assert!(years.contains(&year));
+    }
 }
@@ -0,0 +1,166 @@
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::{message_handling::GuiMessage, state::GuiState},
+    csv,
+    dialog::{change_role::ChangeRoleData, confirmation::ConfirmationDialog},
+    entity_view::category::{read_label_categories, CategoryFilter},
+    errors::LoreGuiError,
+    matrix_view::{MatrixViewMessage, MatrixViewState},
+};
+
+impl GuiState {
+    pub(super) fn update_matrix_view(
+        &mut self,
+        event: MatrixViewMessage,
+    ) -> Result<(), LoreGuiError> {
+        let state = &mut self.matrix_view_state;
+        match event {
+            MatrixViewMessage::RowFilterSelected(filter) => state.row_filter = filter,
+            MatrixViewMessage::ColumnFilterSelected(filter) => state.column_filter = filter,
+            MatrixViewMessage::CellSelected(parent, child) => {
+                state.cell_text = state.get_cell_text(parent.to_str(), child.to_str());
+                state.selected_cell = Some((parent, child));
+            }
+            MatrixViewMessage::CellTextUpdate(text) => state.cell_text = text,
+            MatrixViewMessage::CellSave => self.save_matrix_cell()?,
+            MatrixViewMessage::ExportCsv => {
+                csv::export("relationship_matrix.csv", &state.to_rows())?
+            }
+        };
+        self.matrix_view_state.update(&self.lore_database)?;
+        Ok(())
+    }
+
+    /// Goes through the relationship view's flows, so that deletions and mirroring are offered.
+    fn save_matrix_cell(&mut self) -> Result<(), LoreGuiError> {
+        let state = &self.matrix_view_state;
+        let (parent, child) = match &state.selected_cell {
+            Some(cell) => cell.clone(),
+            None => return Ok(()),
+        };
+        let old_roles = state.get_roles(parent.to_str(), child.to_str());
+        let mut new_roles: Vec<Role> = vec![];
+        for role in state.cell_text.split(',').map(str::trim) {
+            if !role.is_empty() && !new_roles.contains(&role.into()) {
+                new_roles.push(role.into());
+            }
+        }
+        let removed: Vec<&Role> = old_roles
+            .iter()
+            .filter(|r| !new_roles.contains(r))
+            .collect();
+        let added: Vec<&Role> = new_roles
+            .iter()
+            .filter(|r| !old_roles.contains(r))
+            .collect();
+
+        let relationship = |role: &Role| EntityRelationship {
+            parent: parent.clone(),
+            child: child.clone(),
+            role: role.clone(),
+        };
+        match (removed.as_slice(), added.as_slice()) {
+            ([], []) => Ok(()),
+            ([], [role]) => self.write_new_relationship(relationship(*role).into()),
+            ([role], []) => {
+                self.confirm_relationship_deletion(relationship(*role));
+                Ok(())
+            }
+            ([old_role], [new_role]) => {
+                let message = format!(
+                    "Do you really want to change the role of the {} relationship between {} and {} to {}?",
+                    old_role, parent, child, new_role
+                );
+                let mut data = ChangeRoleData::new(relationship(*old_role));
+                data.set_new_role((*new_role).clone());
+                let on_confirm = GuiMessage::ChangeRole(data);
+                self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)));
+                Ok(())
+            }
+            _ => Err(LoreGuiError::InputError(
+                "Add, remove or replace one role at a time.".to_string(),
+            )),
+        }
+    }
+}
+
+impl MatrixViewState {
+    pub(super) fn reset_selections(
+        &mut self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<(), LoreGuiError> {
+        self.row_filter = CategoryFilter::default();
+        self.column_filter = CategoryFilter::default();
+        self.selected_cell = None;
+        self.cell_text = String::new();
+        self.update(db)?;
+        Ok(())
+    }
+
+    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.category_options = match db {
+            Some(db) => CategoryFilter::options(&read_label_categories(db)?),
+            None => vec![],
+        };
+        (self.parents, self.children) = self.get_current_labels(db)?;
+        self.cell_roles = self.get_current_cell_roles(db)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_labels, example_role};
+
+    #[test]
+    fn saving_cell_changes_one_role_after_confirmation() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.matrix_view_state.update(&gui.lore_database).unwrap();
+        let labels = example_labels();
+        let parent: Parent = labels[0].to_str().into();
+        let child: Child = labels[1].to_str().into();
+        let old_role = example_role(&parent, &child);
+        let roles = |gui: &GuiState| {
+            gui.matrix_view_state
+                .get_roles(parent.to_str(), child.to_str())
+        };
+
+        let select = MatrixViewMessage::CellSelected(parent.clone(), child.clone());
+        gui.update_matrix_view(select).unwrap();
+        assert_eq!(gui.matrix_view_state.cell_text, old_role.to_str());
+
+        let text = "ally, rival".to_string();
+        gui.update_matrix_view(MatrixViewMessage::CellTextUpdate(text))
+            .unwrap();
+        assert!(gui.update_matrix_view(MatrixViewMessage::CellSave).is_err());
+
+        let text = "ally, ally".to_string();
+        gui.update_matrix_view(MatrixViewMessage::CellTextUpdate(text))
+            .unwrap();
+        gui.update_matrix_view(MatrixViewMessage::CellSave).unwrap();
+        assert!(gui.dialog.is_some());
+        assert_eq!(roles(&gui), vec![old_role]);
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        assert_eq!(roles(&gui), vec![Role::from("ally")]);
+
+        gui.dialog = None;
+        let text = "ally, rival".to_string();
+        gui.update_matrix_view(MatrixViewMessage::CellTextUpdate(text))
+            .unwrap();
+        gui.update_matrix_view(MatrixViewMessage::CellSave).unwrap();
+        assert_eq!(roles(&gui), vec![Role::from("ally"), Role::from("rival")]);
+
+        let text = "rival".to_string();
+        gui.update_matrix_view(MatrixViewMessage::CellTextUpdate(text))
+            .unwrap();
+        gui.update_matrix_view(MatrixViewMessage::CellSave).unwrap();
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        assert_eq!(roles(&gui), vec![Role::from("rival")]);
+    }
+}
@@ -1,6 +1,9 @@
+use iced::widget::combo_box;
 use lorecore::{
-    extractions::extract_labels,
-    sql::{lore_database::LoreDatabase, search_params::EntityColumnSearchParams},
+    sql::{
+        lore_database::LoreDatabase, relationship::extract_roles,
+        search_params::RelationshipSearchParams,
+    },
     types::*,
 };
 
@@ -10,10 +13,23 @@ use crate::{
     dialog::{
         change_role::{ChangeRoleData, ChangeRoleDialog},
         confirmation::ConfirmationDialog,
+        label_picker::read_label_options,
+        new_inverse_role_pair::{NewInverseRolePairData, NewInverseRolePairDialog},
         new_relationship::{NewRelationshipData, NewRelationshipDialog},
+        rename_role::{RenameRoleData, RenameRoleDialog},
+        retarget_relationship::{RetargetRelationshipData, RetargetRelationshipDialog},
     },
     errors::LoreGuiError,
-    relationship_view::{RelationshipViewMessage, RelationshipViewState},
+    relationship_view::{
+        ancestry::{build_tree, Direction},
+        integrity::find_dangling_relationships,
+        inverse_roles::{
+            inverse_of, inverse_role_pair_key, mirror, read_inverse_role_pairs, relationship_exists,
+        },
+        path::{shortest_paths, MAX_PATHS},
+        roles::read_role_usages,
+        RelationshipViewMessage, RelationshipViewState,
+    },
 };
 
 use super::message_handling::GuiMessage;
@@ -24,24 +40,69 @@ impl GuiState {
         event: RelationshipViewMessage,
     ) -> Result<(), LoreGuiError> {
         match event {
-            RelationshipViewMessage::NewRelationship => {
-                let labels = self.get_all_labels(&self.lore_database)?;
-                self.dialog = Some(Box::new(NewRelationshipDialog::new(
-                    labels.clone().into_iter().map(|l| l.into()).collect(),
-                    labels.clone().into_iter().map(|l| l.into()).collect(),
-                )));
-            }
+            RelationshipViewMessage::NewRelationship => self.open_new_relationship_dialog(None)?,
             RelationshipViewMessage::ChangeRole(data) => {
                 self.dialog = Some(Box::new(ChangeRoleDialog::new(data.clone())));
             }
             RelationshipViewMessage::DeleteRelationship(rel) => {
+                self.confirm_relationship_deletion(rel)
+            }
+            RelationshipViewMessage::RetargetRelationship(rel) => {
+                let db = self
+                    .lore_database
+                    .as_ref()
+                    .ok_or(LoreGuiError::NoDatabase)?;
+                let labels = read_label_options(db)?;
+                let data = RetargetRelationshipData::new(rel);
+                self.dialog = Some(Box::new(RetargetRelationshipDialog::new(data, labels)));
+            }
+            RelationshipViewMessage::NewInverseRolePair => {
+                self.dialog = Some(Box::new(NewInverseRolePairDialog::new()))
+            }
+            RelationshipViewMessage::DeleteInverseRolePair(role) => {
                 let message = format!(
-                    "Do you really want to delete the {} relationship between {} and {}?",
-                    rel.role, rel.parent, rel.child
+                    "Do you really want to delete the inverse role pair of {}?",
+                    role
                 );
-                let on_confirm = GuiMessage::DeleteRelationship(rel);
+                let on_confirm = GuiMessage::DeleteInverseRolePair(role);
+                self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
+            }
+            RelationshipViewMessage::RenameRole(role) => {
+                let roles = self
+                    .relationship_view_state
+                    .role_usages
+                    .iter()
+                    .map(|usage| usage.role.clone())
+                    .collect();
+                let data = RenameRoleData::new(role);
+                self.dialog = Some(Box::new(RenameRoleDialog::new(data, roles)));
+            }
+            RelationshipViewMessage::DeleteRole(role) => {
+                let message = format!("Do you really want to delete the unused role {}?", role);
+                let on_confirm = GuiMessage::DeleteRole(role);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
             }
+            RelationshipViewMessage::AncestryLabelSelected(label) => {
+                self.relationship_view_state.ancestry_label = Some(label);
+            }
+            RelationshipViewMessage::FollowedRoleToggled(role, checked) => {
+                let roles = &mut self.relationship_view_state.followed_roles;
+                roles.retain(|r| r != &role);
+                if checked {
+                    roles.push(role);
+                }
+            }
+            RelationshipViewMessage::PathSourceSelected(label) => {
+                self.relationship_view_state.path_source = Some(label);
+            }
+            RelationshipViewMessage::PathTargetSelected(label) => {
+                self.relationship_view_state.path_target = Some(label);
+            }
+            RelationshipViewMessage::PathStepSelected(rel) => {
+                self.set_selected_parent(Some(rel.parent));
+                self.set_selected_child(Some(rel.child));
+                self.set_selected_role(Some(rel.role));
+            }
             RelationshipViewMessage::ParentViewUpdate(event) => {
                 self.update_parent_view(event)?;
             }
@@ -56,6 +117,25 @@ impl GuiState {
         Ok(())
     }
 
+    pub(super) fn open_new_relationship_dialog(
+        &mut self,
+        parent: Option<Parent>,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let labels = read_label_options(db)?;
+        let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+        let roles = extract_roles(&relationships);
+        let mut dialog = NewRelationshipDialog::new(labels, roles);
+        if let Some(parent) = parent {
+            dialog = dialog.with_parent(parent);
+        }
+        self.dialog = Some(Box::new(dialog));
+        Ok(())
+    }
+
     pub(super) fn update_parent_view(
         &mut self,
         event: ColViewMes<Parent>,
@@ -118,10 +198,14 @@ impl GuiState {
         let child = data.child().clone();
         let role = data.role().clone();
         data.write_to_database(db)?;
-        self.set_selected_parent(Some(parent));
-        self.set_selected_child(Some(child));
-        self.set_selected_role(Some(role));
-        Ok(())
+        self.set_selected_parent(Some(parent.clone()));
+        self.set_selected_child(Some(child.clone()));
+        self.set_selected_role(Some(role.clone()));
+        self.offer_mirrored_creation(EntityRelationship {
+            parent,
+            child,
+            role,
+        })
     }
 
     pub(super) fn change_relationship_role(
@@ -132,14 +216,24 @@ impl GuiState {
             .lore_database
             .as_ref()
             .ok_or(LoreGuiError::NoDatabase)?;
+        let old_relationship = data.old_relationship().clone();
         let parent = data.parent().clone().into();
         let child = data.child().clone().into();
-        let role = data.new_role().clone().into();
+        let new_role = data.new_role().clone();
         data.write_to_database(db)?;
         self.set_selected_parent(parent);
         self.set_selected_child(child);
-        self.set_selected_role(role);
-        Ok(())
+        self.set_selected_role(Some(new_role.clone()));
+        self.offer_mirrored_role_change(old_relationship, new_role)
+    }
+
+    pub(super) fn confirm_relationship_deletion(&mut self, rel: EntityRelationship) {
+        let message = format!(
+            "Do you really want to delete the {} relationship between {} and {}?",
+            rel.role, rel.parent, rel.child
+        );
+        let on_confirm = GuiMessage::DeleteRelationship(rel);
+        self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
     }
 
     pub(super) fn delete_relationship(
@@ -150,22 +244,181 @@ impl GuiState {
             .lore_database
             .as_ref()
             .ok_or(LoreGuiError::NoDatabase)?;
-        db.delete_relationship(rel)?;
+        db.delete_relationship(rel.clone())?;
         self.set_selected_parent(None);
         self.set_selected_child(None);
         self.set_selected_role(None);
+        self.offer_mirrored_deletion(rel)
+    }
+
+    pub(super) fn retarget_relationship(
+        &mut self,
+        data: RetargetRelationshipData,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let parent = data.new_parent().clone();
+        let child = data.new_child().clone();
+        let role = data.role().clone();
+        data.write_to_database(db)?;
+        self.set_selected_parent(Some(parent));
+        self.set_selected_child(Some(child));
+        self.set_selected_role(Some(role));
         Ok(())
     }
 
-    fn get_all_labels(&self, db: &Option<LoreDatabase>) -> Result<Vec<Label>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
+    pub(super) fn rename_role(&mut self, data: RenameRoleData) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let old_role = data.old_role().clone();
+        let new_role = data.new_role().clone();
+        data.write_to_database(db)?;
+        if self.relationship_view_state.get_selected_role() == Some(old_role) {
+            self.set_selected_role(Some(new_role));
+        }
+        Ok(())
+    }
+
+    /// Deleting a role removes its whole inverse pair, so both roles must be unused.
+    pub(super) fn delete_unused_role(&mut self, role: Role) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let usages = read_role_usages(db)?;
+        let is_used = |role: &Role| {
+            usages
+                .iter()
+                .any(|usage| &usage.role == role && usage.count > 0)
+        };
+        if is_used(&role) {
+            return Err(LoreGuiError::InputError(format!(
+                "Role {} is still in use.",
+                role
+            )));
+        }
+        if let Some(inverse) = inverse_of(&read_inverse_role_pairs(db)?, &role) {
+            if is_used(&inverse) {
+                return Err(LoreGuiError::InputError(format!(
+                    "Role {} cannot be deleted, since its inverse role {} is still in use.",
+                    role, inverse
+                )));
+            }
+        }
+        self.delete_inverse_role_pair(role)
+    }
+
+    fn offer_mirrored_creation(&mut self, rel: EntityRelationship) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let pairs = read_inverse_role_pairs(db)?;
+        let mirrored = match mirror(&pairs, &rel) {
+            Some(mirrored) => mirrored,
+            None => return Ok(()),
         };
-        let search_params = EntityColumnSearchParams::new(None, None);
-        let entity_columns = db.read_entity_columns(search_params)?;
-        let labels = extract_labels(&entity_columns);
-        Ok(labels)
+        if relationship_exists(db, &mirrored)? {
+            return Ok(());
+        }
+        let message = format!(
+            "Do you also want to create the {} relationship between {} and {}?",
+            mirrored.role, mirrored.parent, mirrored.child
+        );
+        let on_confirm = GuiMessage::NewRelationship(mirrored.into());
+        self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)));
+        Ok(())
+    }
+
+    fn offer_mirrored_role_change(
+        &mut self,
+        old_relationship: EntityRelationship,
+        new_role: Role,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let pairs = read_inverse_role_pairs(db)?;
+        let mirrored = match mirror(&pairs, &old_relationship) {
+            Some(mirrored) if relationship_exists(db, &mirrored)? => mirrored,
+            _ => {
+                let new_relationship = EntityRelationship {
+                    role: new_role,
+                    ..old_relationship
+                };
+                return self.offer_mirrored_creation(new_relationship);
+            }
+        };
+        let new_inverse = match inverse_of(&pairs, &new_role) {
+            Some(new_inverse) => new_inverse,
+            None => return self.offer_mirrored_deletion(old_relationship),
+        };
+        if mirrored.role == new_inverse {
+            return Ok(());
+        }
+        let message = format!(
+            "Do you also want to change the role of the {} relationship between {} and {} to {}?",
+            mirrored.role, mirrored.parent, mirrored.child, new_inverse
+        );
+        let mut data = ChangeRoleData::new(mirrored);
+        data.set_new_role(new_inverse);
+        let on_confirm = GuiMessage::ChangeRole(data);
+        self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)));
+        Ok(())
+    }
+
+    fn offer_mirrored_deletion(&mut self, rel: EntityRelationship) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let pairs = read_inverse_role_pairs(db)?;
+        let mirrored = match mirror(&pairs, &rel) {
+            Some(mirrored) => mirrored,
+            None => return Ok(()),
+        };
+        if !relationship_exists(db, &mirrored)? {
+            return Ok(());
+        }
+        let message = format!(
+            "Do you also want to delete the {} relationship between {} and {}?",
+            mirrored.role, mirrored.parent, mirrored.child
+        );
+        let on_confirm = GuiMessage::DeleteRelationship(mirrored);
+        self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)));
+        Ok(())
+    }
+
+    pub(super) fn write_new_inverse_role_pair(
+        &mut self,
+        data: NewInverseRolePairData,
+    ) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        data.write_to_database(db)?;
+        Ok(())
+    }
+
+    /// A pair is stored under its first role, but may be deleted via either of its roles.
+    pub(super) fn delete_inverse_role_pair(&mut self, role: Role) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let pairs = read_inverse_role_pairs(db)?;
+        let pair = pairs
+            .into_iter()
+            .find(|pair| pair.involves(&role))
+            .ok_or_else(|| LoreGuiError::InputError(format!("Role {} has no inverse.", role)))?;
+        db.delete_entity_column(inverse_role_pair_key(&pair.role))?;
+        Ok(())
     }
 }
 
@@ -187,6 +440,10 @@ impl RelationshipViewState {
         self.update_parents(db)?;
         self.update_children(db)?;
         self.update_role(db)?;
+        self.update_inverse_role_pairs(db)?;
+        self.update_dangling_relationships(db)?;
+        self.update_role_usages(db)?;
+        self.update_ancestry(db)?;
         Ok(())
     }
 
@@ -219,12 +476,75 @@ impl RelationshipViewState {
         self.role_view_state.set_entries(roles);
         Ok(())
     }
+
+    fn update_dangling_relationships(
+        &mut self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<(), LoreGuiError> {
+        self.dangling_relationships = match db {
+            Some(db) => find_dangling_relationships(db)?,
+            None => vec![],
+        };
+        Ok(())
+    }
+
+    fn update_role_usages(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.role_usages = match db {
+            Some(db) => read_role_usages(db)?,
+            None => vec![],
+        };
+        if !self.display_protected {
+            self.role_usages.retain(|usage| !usage.role.is_protected());
+        }
+        Ok(())
+    }
+
+    fn update_ancestry(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        let options = self.get_ancestry_options(db)?;
+        if self.ancestry_picker.options() != options.as_slice() {
+            self.ancestry_picker = combo_box::State::new(options.clone());
+            self.path_source_picker = combo_box::State::new(options.clone());
+            self.path_target_picker = combo_box::State::new(options);
+        }
+        self.ancestors = vec![];
+        self.descendants = vec![];
+        self.paths = vec![];
+        let path_ends = match (&self.path_source, &self.path_target) {
+            (Some(from), Some(to)) if from != to => Some((from, to)),
+            _ => None,
+        };
+        let db = match db {
+            Some(db) if self.ancestry_label.is_some() || path_ends.is_some() => db,
+            _ => return Ok(()),
+        };
+        let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+        let roles = &self.followed_roles;
+        if let Some(label) = &self.ancestry_label {
+            self.ancestors = build_tree(&relationships, label, roles, Direction::Ancestors);
+            self.descendants = build_tree(&relationships, label, roles, Direction::Descendants);
+        }
+        if let Some((from, to)) = path_ends {
+            // One more than are shown, so that the view can tell that some were left out.
+            self.paths = shortest_paths(&relationships, from, to, roles, MAX_PATHS + 1);
+        }
+        Ok(())
+    }
+
+    fn update_inverse_role_pairs(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.inverse_role_pairs = match db {
+            Some(db) => read_inverse_role_pairs(db)?,
+            None => vec![],
+        };
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    use crate::dialog::new_entity::NewEntityData;
+    use crate::dialog::new_inverse_role_pair::tests::example_new_inverse_role_pair_data;
     use crate::tests::{example_database, example_labels, example_role};
 
     #[test]
@@ -264,4 +584,216 @@ mod test {
         assert_eq!(gui.get_selected_child(), Some(new_child));
         assert_eq!(gui.get_selected_role(), None);
     }
+
+    #[test]
+    fn mirrored_relationship_is_offered_for_inverse_roles() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let pair_data = example_new_inverse_role_pair_data();
+        gui.handle_message(GuiMessage::NewInverseRolePair(pair_data))
+            .unwrap();
+        let rel = EntityRelationship {
+            parent: "teacher".into(),
+            child: "student".into(),
+            role: "mentor".into(),
+        };
+        let mirrored = EntityRelationship {
+            parent: "student".into(),
+            child: "teacher".into(),
+            role: "apprentice".into(),
+        };
+
+        gui.handle_message(GuiMessage::NewRelationship(rel.clone().into()))
+            .unwrap();
+        assert!(gui.dialog.is_some());
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        let db = gui.lore_database.as_ref().unwrap();
+        assert!(relationship_exists(db, &mirrored).unwrap());
+
+        gui.dialog = None;
+        gui.handle_message(GuiMessage::DeleteRelationship(rel))
+            .unwrap();
+        assert!(gui.dialog.is_some());
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        let db = gui.lore_database.as_ref().unwrap();
+        assert!(!relationship_exists(db, &mirrored).unwrap());
+    }
+
+    #[test]
+    fn changing_to_unpaired_role_offers_deleting_the_mirror() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let pair_data = example_new_inverse_role_pair_data();
+        gui.handle_message(GuiMessage::NewInverseRolePair(pair_data))
+            .unwrap();
+        let rel = EntityRelationship {
+            parent: "teacher".into(),
+            child: "student".into(),
+            role: "mentor".into(),
+        };
+        let mirrored = EntityRelationship {
+            parent: "student".into(),
+            child: "teacher".into(),
+            role: "apprentice".into(),
+        };
+        gui.handle_message(GuiMessage::NewRelationship(rel.clone().into()))
+            .unwrap();
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+
+        gui.dialog = None;
+        let mut data = ChangeRoleData::new(rel.clone());
+        data.set_new_role("friend".into());
+        gui.handle_message(GuiMessage::ChangeRole(data)).unwrap();
+        assert!(gui.dialog.is_some());
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        let db = gui.lore_database.as_ref().unwrap();
+        assert!(!relationship_exists(db, &mirrored).unwrap());
+
+        gui.dialog = None;
+        let mut data = ChangeRoleData::new(EntityRelationship {
+            role: "friend".into(),
+            ..rel
+        });
+        data.set_new_role("mentor".into());
+        gui.handle_message(GuiMessage::ChangeRole(data)).unwrap();
+        assert!(gui.dialog.is_some());
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        let db = gui.lore_database.as_ref().unwrap();
+        assert!(relationship_exists(db, &mirrored).unwrap());
+    }
+
+    #[test]
+    fn dangling_relationship_can_be_retargeted_or_stubbed() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let rel = EntityRelationship {
+            parent: labels[0].to_str().into(),
+            child: "ghost".into(),
+            role: "haunts".into(),
+        };
+        gui.handle_message(GuiMessage::NewRelationship(rel.clone().into()))
+            .unwrap();
+        let dangling = &gui.relationship_view_state.dangling_relationships;
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].missing_labels, vec![Label::from("ghost")]);
+
+        let mut data = RetargetRelationshipData::new(rel);
+        data.set_new_child(labels[1].to_str().into());
+        gui.handle_message(GuiMessage::RetargetRelationship(data))
+            .unwrap();
+        assert!(gui
+            .relationship_view_state
+            .dangling_relationships
+            .is_empty());
+        assert_eq!(gui.get_selected_child(), Some(labels[1].to_str().into()));
+
+        let rel = EntityRelationship {
+            parent: labels[0].to_str().into(),
+            child: "ghost".into(),
+            role: "haunts".into(),
+        };
+        gui.handle_message(GuiMessage::NewRelationship(rel.into()))
+            .unwrap();
+        let stub = NewEntityData::stub("ghost".into());
+        gui.handle_message(GuiMessage::NewEntity(stub)).unwrap();
+        assert!(gui
+            .relationship_view_state
+            .dangling_relationships
+            .is_empty());
+    }
+
+    #[test]
+    fn retargeting_onto_existing_relationship_keeps_the_original() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let existing = EntityRelationship {
+            parent: labels[0].to_str().into(),
+            child: labels[1].to_str().into(),
+            role: "haunts".into(),
+        };
+        let rel = EntityRelationship {
+            child: "ghost".into(),
+            ..existing.clone()
+        };
+        for rel in [existing, rel.clone()] {
+            gui.handle_message(GuiMessage::NewRelationship(rel.into()))
+                .unwrap();
+        }
+
+        let mut data = RetargetRelationshipData::new(rel.clone());
+        data.set_new_child(labels[1].to_str().into());
+        assert!(gui
+            .handle_message(GuiMessage::RetargetRelationship(data))
+            .is_err());
+        let db = gui.lore_database.as_ref().unwrap();
+        assert!(relationship_exists(db, &rel).unwrap());
+    }
+
+    #[test]
+    fn renaming_role_to_existing_role_merges_them() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let parent: Parent = labels[0].to_str().into();
+        let child: Child = labels[1].to_str().into();
+        for role in ["father", "Father"] {
+            let rel = EntityRelationship {
+                parent: parent.clone(),
+                child: child.clone(),
+                role: role.into(),
+            };
+            gui.handle_message(GuiMessage::NewRelationship(rel.into()))
+                .unwrap();
+        }
+
+        gui.set_selected_role(Some("mother".into()));
+        let mut data = RenameRoleData::new("Father".into());
+        data.set_new_role("father".into());
+        gui.handle_message(GuiMessage::RenameRole(data)).unwrap();
+        assert_eq!(gui.get_selected_role(), Some("mother".into()));
+
+        let usages = &gui.relationship_view_state.role_usages;
+        assert!(!usages.iter().any(|usage| usage.role == "Father".into()));
+        let father = usages
+            .iter()
+            .find(|usage| usage.role == "father".into())
+            .unwrap();
+        assert_eq!(father.count, 1);
+    }
+
+    #[test]
+    fn role_whose_inverse_is_in_use_cannot_be_deleted() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let pair_data = example_new_inverse_role_pair_data();
+        gui.handle_message(GuiMessage::NewInverseRolePair(pair_data))
+            .unwrap();
+        let rel = EntityRelationship {
+            parent: "student".into(),
+            child: "teacher".into(),
+            role: "apprentice".into(),
+        };
+        gui.handle_message(GuiMessage::NewRelationship(rel.into()))
+            .unwrap();
+
+        assert!(gui
+            .handle_message(GuiMessage::DeleteRole("mentor".into()))
+            .is_err());
+        let db = gui.lore_database.as_ref().unwrap();
+        assert_eq!(read_inverse_role_pairs(db).unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,63 @@
+use lorecore::sql::lore_database::LoreDatabase;
+
+use crate::{errors::LoreGuiError, report_view::ReportViewState};
+
+impl ReportViewState {
+    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.issues = self.get_current_issues(db)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lorecore::types::*;
+
+    use crate::{
+        report_view::issues::QualityIssue,
+        tests::{example_database, example_labels},
+    };
+
+    #[test]
+    fn example_entities_lack_names_and_categories_and_one_looks_alike() {
+        let db = example_database();
+        let labels = example_labels();
+        let misspelled: Label = labels[0].to_str().replacen("label", "lable", 1).into();
+        let misspelled_col = EntityColumn {
+            label: misspelled.clone(),
+            descriptor: "_name".into(),
+            description: "Misspelled".into(),
+        };
+        db.write_entity_columns(vec![misspelled_col]).unwrap();
+        let db = Some(db);
+        let mut state = ReportViewState::default();
+        state.update(&db).unwrap();
+        let count = |matches: fn(&QualityIssue) -> bool| {
+            state.issues.iter().filter(|issue| matches(issue)).count()
+        };
+
+        assert_eq!(
+            count(|i| matches!(i, QualityIssue::MissingName(_))),
+            labels.len()
+        );
+        assert_eq!(
+            count(|i| matches!(i, QualityIssue::MissingCategory(_))),
+            labels.len() + 1
+        );
+        assert_eq!(
+            count(|i| matches!(i, QualityIssue::OnlyProtectedDescriptors(_))),
+            0
+        );
+        let similar: Vec<(&str, &str)> = state
+            .issues
+            .iter()
+            .filter_map(|issue| match issue {
+                QualityIssue::SimilarLabels(a, b) => Some((a.to_str(), b.to_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(similar, vec![(labels[0].to_str(), misspelled.to_str())]);
+    }
+}
@@ -0,0 +1,10 @@
+use lorecore::sql::lore_database::LoreDatabase;
+
+use crate::{errors::LoreGuiError, statistics_view::StatisticsViewState};
+
+impl StatisticsViewState {
+    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.statistics = self.get_current_statistics(db)?;
+        Ok(())
+    }
+}
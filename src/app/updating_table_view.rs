@@ -0,0 +1,177 @@
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::state::GuiState,
+    csv,
+    entity_view::category::{read_label_categories, CategoryFilter},
+    errors::LoreGuiError,
+    table_view::{TableViewMessage, TableViewState},
+};
+
+impl GuiState {
+    pub(super) fn update_table_view(
+        &mut self,
+        event: TableViewMessage,
+    ) -> Result<(), LoreGuiError> {
+        let state = &mut self.table_view_state;
+        match event {
+            TableViewMessage::CategoryFilterSelected(filter) => state.category_filter = filter,
+            TableViewMessage::DescriptorToggled(descriptor, checked) => {
+                state.descriptors.retain(|d| d != &descriptor);
+                if checked {
+                    state.descriptors.push(descriptor);
+                }
+            }
+            TableViewMessage::SortBy(column) => {
+                if state.sort_column == column {
+                    state.sort_descending = !state.sort_descending;
+                } else {
+                    state.sort_column = column;
+                    state.sort_descending = false;
+                }
+            }
+            TableViewMessage::CellSelected(label, descriptor) => {
+                state.cell_text = state.get_cell_text(&label, &descriptor).to_string();
+                state.selected_cell = Some((label, descriptor));
+            }
+            TableViewMessage::CellTextUpdate(text) => state.cell_text = text,
+            TableViewMessage::CellSave => self.save_table_cell()?,
+            TableViewMessage::ExportCsv => csv::export("entity_table.csv", &state.to_rows())?,
+        };
+        self.table_view_state.update(&self.lore_database)?;
+        Ok(())
+    }
+
+    /// An empty description deletes the descriptor.
+    fn save_table_cell(&mut self) -> Result<(), LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let state = &self.table_view_state;
+        let (label, descriptor) = match &state.selected_cell {
+            Some(cell) => cell.clone(),
+            None => return Ok(()),
+        };
+        let exists = state
+            .cells
+            .get(label.to_str())
+            .is_some_and(|descriptions| descriptions.contains_key(descriptor.to_str()));
+        let description: Description = state.cell_text.as_str().into();
+        match (exists, description.to_str().is_empty()) {
+            (true, true) => db.delete_entity_column((label, descriptor))?,
+            (true, false) => db.change_entity_description((&label, &descriptor), &description)?,
+            (false, false) => db.write_entity_columns(vec![EntityColumn {
+                label,
+                descriptor,
+                description,
+            }])?,
+            (false, true) => (),
+        }
+        Ok(())
+    }
+}
+
+impl TableViewState {
+    pub(super) fn reset_selections(
+        &mut self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<(), LoreGuiError> {
+        self.category_filter = CategoryFilter::default();
+        self.descriptors = vec![];
+        self.sort_column = None;
+        self.sort_descending = false;
+        self.selected_cell = None;
+        self.cell_text = String::new();
+        self.update(db)?;
+        Ok(())
+    }
+
+    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
+        self.category_options = match db {
+            Some(db) => CategoryFilter::options(&read_label_categories(db)?),
+            None => vec![],
+        };
+        self.entity_columns = self.get_current_entity_columns(db)?;
+        self.descriptor_options = Self::get_descriptor_options(&self.entity_columns);
+        self.cells = Self::get_cells(&self.entity_columns);
+        self.labels = self.get_current_labels(db)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::app::message_handling::GuiMessage;
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    #[test]
+    fn saving_cells_adds_changes_and_deletes_descriptions() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.table_view_state.update(&gui.lore_database).unwrap();
+        let label = example_labels()[0].clone();
+        let descriptor = example_descriptors()[0].0.clone();
+        let new_descriptor: Descriptor = "Age".into();
+        let mut save = |descriptor: &Descriptor, text: &str| {
+            let select = TableViewMessage::CellSelected(label.clone(), descriptor.clone());
+            gui.update_table_view(select).unwrap();
+            let update = TableViewMessage::CellTextUpdate(text.to_string());
+            gui.update_table_view(update).unwrap();
+            gui.update_table_view(TableViewMessage::CellSave).unwrap();
+            gui.table_view_state
+                .entity_columns
+                .iter()
+                .find(|col| col.label == label && &col.descriptor == descriptor)
+                .map(|col| col.description.to_str().to_string())
+        };
+
+        assert_eq!(save(&descriptor, "changed"), Some("changed".to_string()));
+        assert_eq!(save(&new_descriptor, "42"), Some("42".to_string()));
+        assert_eq!(save(&descriptor, ""), None);
+    }
+
+    #[test]
+    fn sorting_by_label_again_reverses_the_rows() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.table_view_state.update(&gui.lore_database).unwrap();
+        let mut labels = example_labels();
+        labels.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+        assert_eq!(gui.table_view_state.labels, labels);
+
+        gui.update_table_view(TableViewMessage::SortBy(None))
+            .unwrap();
+        labels.reverse();
+        assert_eq!(gui.table_view_state.labels, labels);
+    }
+
+    #[test]
+    fn protected_descriptors_are_only_offered_when_displayed() {
+        let mut gui = GuiState {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let db = gui.lore_database.as_ref().unwrap();
+        db.write_entity_columns(vec![EntityColumn {
+            label: example_labels()[0].clone(),
+            descriptor: "_category".into(),
+            description: "person".into(),
+        }])
+        .unwrap();
+        let protected: Descriptor = "_category".into();
+
+        gui.table_view_state.update(&gui.lore_database).unwrap();
+        assert!(!gui.table_view_state.descriptor_options.contains(&protected));
+
+        gui.handle_message(GuiMessage::SetDisplayProtected(true))
+            .unwrap();
+        assert!(gui.table_view_state.descriptor_options.contains(&protected));
+    }
+}
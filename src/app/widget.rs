@@ -6,11 +6,13 @@ use crate::{
     dialog::error::ErrorDialog,
     entity_view,
     errors::LoreGuiError,
-    history_view, relationship_view,
+    family_tree_view, history_view, matrix_view, relationship_view, report_view, statistics_view,
+    table_view,
 };
 use iced::{
+    event, keyboard,
     widget::{button, opaque, stack, Button, Column, Container, Row, Text, Toggler},
-    Alignment, Element, Length,
+    Alignment, Element, Event, Length, Subscription,
 };
 
 impl GuiState {
@@ -47,6 +49,19 @@ impl GuiState {
                         &self.relationship_view_state,
                     ))
                 }
+                ViewType::Matrix => {
+                    col = col.push(matrix_view::widget::new(&self.matrix_view_state))
+                }
+                ViewType::FamilyTree => {
+                    col = col.push(family_tree_view::widget::new(&self.family_tree_view_state))
+                }
+                ViewType::Table => col = col.push(table_view::widget::new(&self.table_view_state)),
+                ViewType::Report => {
+                    col = col.push(report_view::widget::new(&self.report_view_state))
+                }
+                ViewType::Statistics => {
+                    col = col.push(statistics_view::widget::new(&self.statistics_view_state))
+                }
             }
         }
         col.height(Length::Fill).into()
@@ -56,6 +71,8 @@ impl GuiState {
         Row::new()
             .push(Button::new("New Lore Database").on_press(GuiMessage::NewDatabase))
             .push(Button::new("Open Lore Database").on_press(GuiMessage::OpenDatabase))
+            .push(Button::new("Find and Replace").on_press(GuiMessage::FindAndReplace))
+            .push(Button::new("Link Mentions").on_press(GuiMessage::SuggestLinks(None)))
             .align_y(Alignment::Center)
             .width(Length::Fill)
             .padding(5)
@@ -93,10 +110,25 @@ impl GuiState {
             .on_press(GuiMessage::ViewSelected(ViewType::History));
         let relationships_button = button(Text::new("Relationships"))
             .on_press(GuiMessage::ViewSelected(ViewType::Relationship));
+        let matrix_button =
+            button(Text::new("Matrix")).on_press(GuiMessage::ViewSelected(ViewType::Matrix));
+        let family_tree_button = button(Text::new("Family Tree"))
+            .on_press(GuiMessage::ViewSelected(ViewType::FamilyTree));
+        let table_button =
+            button(Text::new("Table")).on_press(GuiMessage::ViewSelected(ViewType::Table));
+        let report_button = button(Text::new("Quality Report"))
+            .on_press(GuiMessage::ViewSelected(ViewType::Report));
+        let statistics_button = button(Text::new("Statistics"))
+            .on_press(GuiMessage::ViewSelected(ViewType::Statistics));
         Row::new()
             .push(entity_button)
             .push(history_items_button)
             .push(relationships_button)
+            .push(matrix_button)
+            .push(family_tree_button)
+            .push(table_button)
+            .push(report_button)
+            .push(statistics_button)
             .width(Length::Fill)
             .padding(5)
             .spacing(5)
@@ -109,6 +141,16 @@ impl GuiState {
         Ok(())
     }
 
+    /// Held modifier keys turn clicks in the column views into multi-selections.
+    pub(crate) fn subscription(&self) -> Subscription<GuiMessage> {
+        event::listen_with(|event, _status, _window| match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(GuiMessage::ModifiersChanged(modifiers))
+            }
+            _ => None,
+        })
+    }
+
     pub(crate) fn theme(&self) -> iced::Theme {
         iced::Theme::Dark
     }
@@ -0,0 +1,53 @@
+use crate::{errors::LoreGuiError, file_dialogs};
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub(crate) fn to_csv(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|field| escape(field))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Cancelling the file dialog is not an error.
+pub(crate) fn export(file_name: &str, rows: &[Vec<String>]) -> Result<(), LoreGuiError> {
+    let path = match file_dialogs::export_csv(file_name) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+    std::fs::write(&path, to_csv(rows)).map_err(|e| {
+        LoreGuiError::FileError(format!(
+            "Could not write to {}:\n{}",
+            path.to_string_lossy(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_with_separators_are_quoted() {
+        let rows = vec![
+            vec!["plain".to_string(), "with, comma".to_string()],
+            vec!["say \"hi\"".to_string(), String::new()],
+        ];
+        assert_eq!(
+            to_csv(&rows),
+            "plain,\"with, comma\"\n\"say \"\"hi\"\"\",\n"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use iced::keyboard::Modifiers;
+
 use crate::errors::LoreGuiError;
 
 use super::entry::DbColViewEntry;
@@ -7,15 +9,21 @@ pub(crate) struct DbColViewState<E> {
     search_text: String,
     entries: Vec<DbColViewEntry<E>>,
     selected_entry: DbColViewEntry<E>,
+    /// Entries selected in addition to `selected_entry` by Ctrl- or Shift-clicking.
+    multi_selection: Vec<DbColViewEntry<E>>,
+    /// The index that a Shift-click selects a range from.
+    anchor: Option<usize>,
     add_none_option: bool,
 }
 
-impl<E: Clone> DbColViewState<E> {
+impl<E: Clone + PartialEq> DbColViewState<E> {
     pub(crate) fn new(entries: Vec<DbColViewEntry<E>>, add_none_option: bool) -> Self {
         let mut state = DbColViewState {
             search_text: String::new(),
             entries: vec![],
             selected_entry: DbColViewEntry::NONE,
+            multi_selection: vec![],
+            anchor: None,
             add_none_option,
         };
         state.set_entries(entries);
@@ -26,23 +34,84 @@ impl<E: Clone> DbColViewState<E> {
         if self.add_none_option {
             entries.insert(0, DbColViewEntry::NONE);
         }
+        self.multi_selection.retain(|entry| entries.contains(entry));
+        if entries != self.entries {
+            self.anchor = None;
+        }
         self.entries = entries;
     }
 
-    pub(super) fn get_entries(&self) -> &Vec<DbColViewEntry<E>> {
+    pub(crate) fn get_entries(&self) -> &Vec<DbColViewEntry<E>> {
         &self.entries
     }
 
     pub(crate) fn set_selected(&mut self, entry: DbColViewEntry<E>) {
         self.selected_entry = entry;
+        self.multi_selection = vec![];
+        self.anchor = None;
+    }
+
+    /// Ctrl toggles the entry, Shift selects the range from the previous click.
+    pub(crate) fn select(&mut self, index: usize, entry: DbColViewEntry<E>, modifiers: Modifiers) {
+        if modifiers.shift() {
+            if let Some(anchor) = self.anchor {
+                let last = self.entries.len().saturating_sub(1);
+                let range = anchor.min(index).min(last)..=anchor.max(index).min(last);
+                self.multi_selection = self
+                    .entries
+                    .get(range)
+                    .into_iter()
+                    .flatten()
+                    .filter(|e| e.0.is_some())
+                    .cloned()
+                    .collect();
+                self.selected_entry = entry;
+                return;
+            }
+        }
+        if modifiers.command() {
+            if self.multi_selection.is_empty() && self.selected_entry.0.is_some() {
+                self.multi_selection.push(self.selected_entry.clone());
+            }
+            if self.multi_selection.contains(&entry) {
+                self.multi_selection.retain(|e| e != &entry);
+                self.selected_entry = match self.multi_selection.last() {
+                    Some(last) => last.clone(),
+                    None => DbColViewEntry::NONE,
+                };
+            } else {
+                if entry.0.is_some() {
+                    self.multi_selection.push(entry.clone());
+                }
+                self.selected_entry = entry;
+            }
+            self.anchor = Some(index);
+            return;
+        }
+        self.set_selected(entry);
+        self.anchor = Some(index);
+    }
+
+    /// All selected values, which is just the selected entry unless several were clicked.
+    pub(crate) fn get_multi_selected(&self) -> Vec<E> {
+        if self.multi_selection.is_empty() {
+            self.selected_entry.0.iter().cloned().collect()
+        } else {
+            self.multi_selection
+                .iter()
+                .filter_map(|entry| entry.0.clone())
+                .collect()
+        }
     }
 
     pub(crate) fn get_selected(&self) -> &DbColViewEntry<E> {
         &self.selected_entry
     }
 
+    /// The entries are filtered anew, so the index of the previous click no longer applies.
     pub(crate) fn set_search_text(&mut self, text: String) {
         self.search_text = text;
+        self.anchor = None;
     }
 
     pub(crate) fn get_search_text(&self) -> Option<&str> {
@@ -64,8 +133,53 @@ impl<E: Clone> DbColViewState<E> {
     }
 }
 
-impl<E: Clone> Default for DbColViewState<E> {
+impl<E: Clone + PartialEq> Default for DbColViewState<E> {
     fn default() -> Self {
         Self::new(vec![], false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_state() -> DbColViewState<i32> {
+        let entries = (0..5).map(DbColViewEntry::from).collect();
+        DbColViewState::new(entries, true)
+    }
+
+    #[test]
+    fn ctrl_and_shift_clicks_extend_the_selection() {
+        let mut state = example_state();
+        state.select(1, 0.into(), Modifiers::empty());
+        state.select(3, 2.into(), Modifiers::COMMAND);
+        assert_eq!(state.get_multi_selected(), vec![0, 2]);
+        state.select(1, 0.into(), Modifiers::COMMAND);
+        assert_eq!(state.get_multi_selected(), vec![2]);
+        assert_eq!(state.get_selected(), &DbColViewEntry(Some(2)));
+
+        state.select(5, 4.into(), Modifiers::SHIFT);
+        assert_eq!(state.get_multi_selected(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(state.get_selected(), &DbColViewEntry(Some(4)));
+
+        state.select(2, 1.into(), Modifiers::empty());
+        assert_eq!(state.get_multi_selected(), vec![1]);
+        state.select(2, 1.into(), Modifiers::COMMAND);
+        assert!(state.get_multi_selected().is_empty());
+        assert_eq!(state.get_selected(), &DbColViewEntry::NONE);
+    }
+
+    #[test]
+    fn shift_click_after_filtering_does_not_use_the_old_index() {
+        let mut state = DbColViewState::new((0..20).map(DbColViewEntry::from).collect(), true);
+        state.select(10, 9.into(), Modifiers::empty());
+        state.set_search_text("1".to_string());
+        let filtered = [1, 10, 11].into_iter().map(DbColViewEntry::from).collect();
+        state.set_entries(filtered);
+
+        state.select(1, 1.into(), Modifiers::SHIFT);
+        assert_eq!(state.get_multi_selected(), vec![1]);
+        state.select(3, 11.into(), Modifiers::SHIFT);
+        assert_eq!(state.get_multi_selected(), vec![1, 10, 11]);
+    }
+}
@@ -6,8 +6,9 @@ use iced::{
 use iced_aw::{style, SelectionList};
 use std::fmt::Display;
 use std::hash::Hash;
+use std::ops::Range;
 
-use super::{state::DbColViewState, ColViewMes};
+use super::{entry::DbColViewEntry, state::DbColViewState, ColViewMes};
 use crate::{app::message_handling::GuiMessage, style::header};
 
 pub(crate) fn new<'a, M, E>(
@@ -18,38 +19,88 @@ pub(crate) fn new<'a, M, E>(
 where
     M: 'static + Clone + Fn(ColViewMes<E>) -> GuiMessage,
     E: 'static + Clone + Display + Eq + Hash,
+{
+    let entries = state.get_entries();
+    search_header(title, gui_message.clone(), state)
+        .push(selection_list(gui_message, entries, 0))
+        .into()
+}
+
+/// Like `new`, but splits the entries into consecutive groups, each preceded by its own header.
+pub(crate) fn new_grouped<'a, M, E>(
+    title: &'static str,
+    gui_message: M,
+    state: &'a DbColViewState<E>,
+    groups: Vec<(Element<'a, GuiMessage>, Range<usize>)>,
+) -> Element<'a, GuiMessage>
+where
+    M: 'static + Clone + Fn(ColViewMes<E>) -> GuiMessage,
+    E: 'static + Clone + Display + Eq + Hash,
+{
+    let entries = state.get_entries();
+    let mut col = search_header(title, gui_message.clone(), state);
+    for (group_header, range) in groups {
+        col = col.push(group_header).push(selection_list(
+            gui_message.clone(),
+            &entries[range.clone()],
+            range.start,
+        ));
+    }
+    col.into()
+}
+
+fn search_header<'a, M, E>(
+    title: &'static str,
+    gui_message: M,
+    state: &'a DbColViewState<E>,
+) -> Column<'a, GuiMessage>
+where
+    M: 'static + Clone + Fn(ColViewMes<E>) -> GuiMessage,
+    E: 'static + Clone + Display + PartialEq,
 {
     let title = header(title);
 
-    let selected_text = Text::new(format!("Selected: {}", state.get_selected()));
+    let selected_count = state.get_multi_selected().len();
+    let selected_text = if selected_count > 1 {
+        Text::new(format!("Selected: {} entries", selected_count))
+    } else {
+        Text::new(format!("Selected: {}", state.get_selected()))
+    };
 
     let search_text = state.get_search_text().unwrap_or("");
-    let m = gui_message.clone();
-    let search_field_updated = move |s| m(ColViewMes::SearchFieldUpd(s));
+    let search_field_updated = move |s| gui_message(ColViewMes::SearchFieldUpd(s));
     let search_field = TextInput::new("Type to search...", search_text)
         .on_input(search_field_updated)
         .width(Length::Fill);
 
-    let m = gui_message.clone();
-    let selection_list_updated = move |i, e| m(ColViewMes::Selected(i, e));
-    let selection_list = SelectionList::new_with(
-        state.get_entries(),
-        selection_list_updated,
-        20.0,
-        0.0,
-        style::selection_list::primary,
-        None,
-        Font::default(),
-    );
-
     Column::new()
         .push(title)
         .push(selected_text)
         .push(search_field)
-        .push(selection_list)
         .width(Length::Fill)
         .height(Length::Fill)
         .padding(5)
         .spacing(5)
-        .into()
+}
+
+fn selection_list<'a, M, E>(
+    gui_message: M,
+    entries: &'a [DbColViewEntry<E>],
+    offset: usize,
+) -> Element<'a, GuiMessage>
+where
+    M: 'static + Clone + Fn(ColViewMes<E>) -> GuiMessage,
+    E: 'static + Clone + Display + Eq + Hash,
+{
+    let selection_list_updated = move |i, e| gui_message(ColViewMes::Selected(offset + i, e));
+    SelectionList::new_with(
+        entries,
+        selection_list_updated,
+        20.0,
+        0.0,
+        style::selection_list::primary,
+        None,
+        Font::default(),
+    )
+    .into()
 }
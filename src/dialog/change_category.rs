@@ -0,0 +1,137 @@
+use iced::{
+    widget::{Button, Column, Text, TextInput},
+    Element,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::{
+    app::message_handling::GuiMessage, entity_view::category::CATEGORY_DESCRIPTOR,
+    errors::LoreGuiError,
+};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChangeCategoryDialog {
+    data: ChangeCategoryData,
+}
+
+impl ChangeCategoryDialog {
+    pub(crate) fn new(data: ChangeCategoryData) -> Self {
+        ChangeCategoryDialog { data }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChangeCategoryData {
+    pub(self) labels: Vec<Label>,
+    pub(self) category: String,
+}
+
+impl ChangeCategoryData {
+    pub(crate) fn new(labels: Vec<Label>) -> Self {
+        ChangeCategoryData {
+            labels,
+            category: String::new(),
+        }
+    }
+
+    /// Entities without a category descriptor get one.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.category.is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Cannot change to an empty category.".to_string(),
+            ));
+        }
+        let descriptor: Descriptor = CATEGORY_DESCRIPTOR.into();
+        let description: Description = self.category.into();
+        let mut missing = vec![];
+        for label in self.labels {
+            let search_params = EntityColumnSearchParams::new(
+                Some(SqlSearchText::exact(label.to_str())),
+                Some(SqlSearchText::exact(CATEGORY_DESCRIPTOR)),
+            );
+            if db.read_entity_columns(search_params)?.is_empty() {
+                missing.push(EntityColumn {
+                    label,
+                    descriptor: descriptor.clone(),
+                    description: description.clone(),
+                });
+            } else {
+                db.change_entity_description((&label, &descriptor), &description)?;
+            }
+        }
+        if !missing.is_empty() {
+            db.write_entity_columns(missing)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dialog for ChangeCategoryDialog {
+    fn header(&self) -> String {
+        format!("Change category of {} entities", self.data.labels.len())
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let labels = self
+            .data
+            .labels
+            .iter()
+            .map(|label| label.to_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let category_input = TextInput::new("", &self.data.category)
+            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Category(s)));
+        let submit_button = Button::new(Text::new("Change")).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new(labels))
+            .push(Text::new("New Category:"))
+            .push(category_input)
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::Category(category) = message {
+            self.data.category = category;
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::ChangeCategory(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::entity_view::category::read_label_categories;
+    use crate::tests::{example_database, example_labels};
+
+    #[test]
+    fn category_is_changed_or_added_for_all_labels() {
+        let db = example_database();
+        let labels = example_labels();
+        let mut data = ChangeCategoryData::new(labels[..1].to_vec());
+        data.category = "old".to_string();
+        data.write_to_database(&db).unwrap();
+
+        let mut data = ChangeCategoryData::new(labels[..2].to_vec());
+        data.category = "new".to_string();
+        data.write_to_database(&db).unwrap();
+
+        let categories = read_label_categories(&db).unwrap();
+        assert_eq!(categories.len(), 2);
+        assert!(categories.iter().all(|(_, category)| category == "new"));
+    }
+}
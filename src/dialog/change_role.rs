@@ -38,6 +38,10 @@ impl ChangeRoleData {
         Ok(())
     }
 
+    pub(crate) fn old_relationship(&self) -> &EntityRelationship {
+        &self.old_relationship
+    }
+
     pub(crate) fn parent(&self) -> &Parent {
         &self.old_relationship.parent
     }
@@ -50,7 +54,6 @@ impl ChangeRoleData {
         &self.new_role
     }
 
-    #[cfg(test)]
     pub(crate) fn set_new_role(&mut self, role: Role) {
         self.new_role = role;
     }
@@ -0,0 +1,197 @@
+use iced::{
+    widget::{Button, Checkbox, Column, Text, TextInput},
+    Element,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, RelationshipSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct DuplicateEntityDialog {
+    data: DuplicateEntityData,
+}
+
+impl DuplicateEntityDialog {
+    pub(crate) fn new(data: DuplicateEntityData) -> Self {
+        DuplicateEntityDialog { data }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DuplicateEntityData {
+    pub(self) original_label: Label,
+    pub(self) new_label: Label,
+    pub(self) include_relationships: bool,
+}
+
+impl DuplicateEntityData {
+    pub(crate) fn new(original_label: Label) -> Self {
+        DuplicateEntityData {
+            new_label: format!("{}_copy", original_label).into(),
+            original_label,
+            include_relationships: false,
+        }
+    }
+
+    /// Copies every descriptor, and optionally every relationship, of the original to the new label.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.new_label.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Cannot duplicate entity with empty new label.".to_string(),
+            ));
+        }
+        let label_search = |label: &Label| {
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None)
+        };
+        if !db
+            .read_entity_columns(label_search(&self.new_label))?
+            .is_empty()
+        {
+            return Err(LoreGuiError::InputError(format!(
+                "An entity with label {} already exists.",
+                self.new_label
+            )));
+        }
+
+        let columns: Vec<EntityColumn> = db
+            .read_entity_columns(label_search(&self.original_label))?
+            .into_iter()
+            .map(|col| EntityColumn {
+                label: self.new_label.clone(),
+                ..col
+            })
+            .collect();
+        if columns.is_empty() {
+            return Err(LoreGuiError::InputError(format!(
+                "Entity {} does not exist.",
+                self.original_label
+            )));
+        }
+        db.write_entity_columns(columns)?;
+
+        if self.include_relationships {
+            let relationships = self.copied_relationships(db)?;
+            if !relationships.is_empty() {
+                db.write_relationships(relationships)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn copied_relationships(
+        &self,
+        db: &LoreDatabase,
+    ) -> Result<Vec<EntityRelationship>, LoreGuiError> {
+        let original = self.original_label.to_str();
+        let replace = |label: &str| {
+            if label == original {
+                self.new_label.to_str().to_string()
+            } else {
+                label.to_string()
+            }
+        };
+        let as_parent = RelationshipSearchParams::new(Some(SqlSearchText::exact(original)), None);
+        let as_child = RelationshipSearchParams::new(None, Some(SqlSearchText::exact(original)));
+        let mut relationships = db.read_relationships(as_parent)?;
+        let incoming = db.read_relationships(as_child)?;
+        relationships.extend(
+            incoming
+                .into_iter()
+                .filter(|rel| rel.parent.to_str() != original),
+        );
+        let copies = relationships
+            .into_iter()
+            .map(|rel| EntityRelationship {
+                parent: replace(rel.parent.to_str()).as_str().into(),
+                child: replace(rel.child.to_str()).as_str().into(),
+                role: rel.role,
+            })
+            .collect();
+        Ok(copies)
+    }
+
+    pub(crate) fn new_label(&self) -> &Label {
+        &self.new_label
+    }
+}
+
+impl Dialog for DuplicateEntityDialog {
+    fn header(&self) -> String {
+        format!("Duplicate entity: {}", self.data.original_label)
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let new_label_input = TextInput::new("", self.data.new_label.to_str())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())));
+        let relationships_input = Checkbox::new(self.data.include_relationships)
+            .label("Include relationships")
+            .on_toggle(|b| GuiMessage::DialogUpdate(DialogUpdate::IncludeRelationships(b)));
+        let submit_button = Button::new(Text::new("Duplicate")).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new("New Label"))
+            .push(new_label_input)
+            .push(relationships_input)
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Label(new_label) => self.data.new_label = new_label,
+            DialogUpdate::IncludeRelationships(include) => {
+                self.data.include_relationships = include
+            }
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::DuplicateEntity(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    #[test]
+    fn duplicate_copies_descriptors_and_relationships() {
+        let db = example_database();
+        let labels = example_labels();
+        let mut data = DuplicateEntityData::new(labels[0].clone());
+        data.include_relationships = true;
+        let new_label = data.new_label().clone();
+        data.clone().write_to_database(&db).unwrap();
+
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(new_label.to_str())), None);
+        let columns = db.read_entity_columns(search_params).unwrap();
+        assert_eq!(columns.len(), example_descriptors().len());
+        let as_parent =
+            RelationshipSearchParams::new(Some(SqlSearchText::exact(new_label.to_str())), None);
+        let as_child =
+            RelationshipSearchParams::new(None, Some(SqlSearchText::exact(new_label.to_str())));
+        assert_eq!(
+            db.read_relationships(as_parent).unwrap().len(),
+            labels.len() - 1
+        );
+        assert_eq!(
+            db.read_relationships(as_child).unwrap().len(),
+            labels.len() - 1
+        );
+
+        assert!(data.write_to_database(&db).is_err());
+    }
+}
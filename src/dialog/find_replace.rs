@@ -0,0 +1,450 @@
+use std::fmt::Display;
+
+use iced::{
+    widget::{Button, Checkbox, Column, Row, Text, TextInput},
+    Element,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, HistoryItemSearchParams},
+    },
+    types::*,
+};
+use regex::{Regex, RegexBuilder};
+
+use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+
+use super::{Dialog, DialogUpdate};
+
+/// How many characters around a match are shown as its context.
+const CONTEXT_LENGTH: usize = 30;
+/// How many matches are listed individually at most.
+const MAX_LISTED_MATCHES: usize = 50;
+
+#[derive(Debug, Clone)]
+pub(crate) struct FindReplaceDialog {
+    data: FindReplaceData,
+}
+
+impl FindReplaceDialog {
+    pub(crate) fn new(data: FindReplaceData) -> Self {
+        FindReplaceDialog { data }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum TextLocation {
+    Description(Label, Descriptor),
+    HistoryContent(Year, Day, Timestamp),
+}
+
+impl Display for TextLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextLocation::Description(label, descriptor) => write!(f, "{}: {}", label, descriptor),
+            TextLocation::HistoryContent(year, day, _) => {
+                write!(f, "History item of year {}, day {}", year, day)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TextSource {
+    pub(crate) location: TextLocation,
+    pub(crate) text: String,
+}
+
+/// All descriptions and history item contents of the database.
+pub(crate) fn read_text_sources(db: &LoreDatabase) -> Result<Vec<TextSource>, LoreGuiError> {
+    let entity_columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+    let history_items =
+        db.read_history_items(HistoryItemSearchParams::new(None, None, None, None))?;
+    let descriptions = entity_columns.into_iter().map(|col| TextSource {
+        location: TextLocation::Description(col.label, col.descriptor),
+        text: col.description.to_str().to_string(),
+    });
+    let contents = history_items.into_iter().map(|item| TextSource {
+        location: TextLocation::HistoryContent(item.year, item.day, item.timestamp),
+        text: item.content.to_str().to_string(),
+    });
+    Ok(descriptions.chain(contents).collect())
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TextMatch {
+    pub(self) source: usize,
+    pub(self) start: usize,
+    pub(self) end: usize,
+    pub(self) replacement: String,
+    pub(self) accepted: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FindReplaceData {
+    pub(self) sources: Vec<TextSource>,
+    pub(self) search_text: String,
+    pub(self) replacement: String,
+    pub(self) case_sensitive: bool,
+    pub(self) whole_word: bool,
+    pub(self) use_regex: bool,
+    pub(self) matches: Vec<TextMatch>,
+    pub(self) error: Option<String>,
+    /// The search or replacement text was edited since the matches were found.
+    pub(self) outdated: bool,
+}
+
+impl FindReplaceData {
+    pub(crate) fn new(sources: Vec<TextSource>) -> Self {
+        FindReplaceData {
+            sources,
+            ..Default::default()
+        }
+    }
+
+    fn build_regex(&self) -> Result<Regex, regex::Error> {
+        let mut pattern = if self.use_regex {
+            self.search_text.clone()
+        } else {
+            regex::escape(&self.search_text)
+        };
+        if self.whole_word {
+            pattern = format!(r"\b(?:{})\b", pattern);
+        }
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+    }
+
+    /// Finds all matches anew, accepting every one of them.
+    fn search(&mut self) {
+        self.matches = vec![];
+        self.error = None;
+        self.outdated = false;
+        if self.search_text.is_empty() {
+            return;
+        }
+        let regex = match self.build_regex() {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+        for (source, text_source) in self.sources.iter().enumerate() {
+            for captures in regex.captures_iter(&text_source.text) {
+                let found = match captures.get(0) {
+                    Some(found) if !found.is_empty() => found,
+                    _ => continue,
+                };
+                let replacement = if self.use_regex {
+                    let mut expanded = String::new();
+                    captures.expand(&self.replacement, &mut expanded);
+                    expanded
+                } else {
+                    self.replacement.clone()
+                };
+                self.matches.push(TextMatch {
+                    source,
+                    start: found.start(),
+                    end: found.end(),
+                    replacement,
+                    accepted: true,
+                });
+            }
+        }
+    }
+
+    /// The match, its replacement and a few characters before and after.
+    fn context(&self, text_match: &TextMatch) -> String {
+        let text = &self.sources[text_match.source].text;
+        let before = &text[..text_match.start];
+        let before_start = before
+            .char_indices()
+            .rev()
+            .nth(CONTEXT_LENGTH)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let after: String = text[text_match.end..]
+            .chars()
+            .take(CONTEXT_LENGTH)
+            .collect();
+        format!(
+            "...{}[{} -> {}]{}...",
+            &before[before_start..],
+            &text[text_match.start..text_match.end],
+            text_match.replacement,
+            after
+        )
+        .replace('\n', " ")
+    }
+
+    fn accepted_count(&self) -> usize {
+        self.matches.iter().filter(|m| m.accepted).count()
+    }
+
+    /// Replaces all accepted matches, writing each changed text once.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.outdated {
+            return Err(LoreGuiError::InputError(
+                "Search again before replacing, the matches are outdated.".to_string(),
+            ));
+        }
+        for (index, source) in self.sources.iter().enumerate() {
+            let accepted: Vec<&TextMatch> = self
+                .matches
+                .iter()
+                .filter(|m| m.source == index && m.accepted)
+                .collect();
+            if accepted.is_empty() {
+                continue;
+            }
+            let ranges = accepted
+                .into_iter()
+                .map(|m| (m.start, m.end, m.replacement.as_str()))
+                .collect();
+            write_text(db, &source.location, &replace_ranges(&source.text, ranges))?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces non-overlapping byte ranges of the text.
+pub(crate) fn replace_ranges(text: &str, mut ranges: Vec<(usize, usize, &str)>) -> String {
+    ranges.sort_by_key(|(start, _, _)| *start);
+    let mut new_text = String::new();
+    let mut position = 0;
+    for (start, end, replacement) in ranges {
+        new_text.push_str(&text[position..start]);
+        new_text.push_str(replacement);
+        position = end;
+    }
+    new_text.push_str(&text[position..]);
+    new_text
+}
+
+pub(crate) fn write_text(
+    db: &LoreDatabase,
+    location: &TextLocation,
+    text: &str,
+) -> Result<(), LoreGuiError> {
+    match location {
+        TextLocation::Description(label, descriptor) => {
+            db.change_entity_description((label, descriptor), &Description::from(text))?
+        }
+        TextLocation::HistoryContent(_, _, timestamp) => {
+            db.change_history_item_content(*timestamp, &HistoryItemContent::from(text))?
+        }
+    }
+    Ok(())
+}
+
+impl Dialog for FindReplaceDialog {
+    fn header(&self) -> String {
+        "Find and replace in all descriptions and history items".to_string()
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let search = GuiMessage::DialogUpdate(DialogUpdate::Search);
+        let search_input = TextInput::new("", &self.data.search_text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::SearchText(i)))
+            .on_submit(search.clone());
+        let replacement_input = TextInput::new("", &self.data.replacement)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Replacement(i)))
+            .on_submit(search.clone());
+        let options = Row::new()
+            .push(
+                Checkbox::new(self.data.case_sensitive)
+                    .label("Case sensitive")
+                    .on_toggle(|b| GuiMessage::DialogUpdate(DialogUpdate::CaseSensitive(b))),
+            )
+            .push(
+                Checkbox::new(self.data.whole_word)
+                    .label("Whole word")
+                    .on_toggle(|b| GuiMessage::DialogUpdate(DialogUpdate::WholeWord(b))),
+            )
+            .push(
+                Checkbox::new(self.data.use_regex)
+                    .label("Regular expression")
+                    .on_toggle(|b| GuiMessage::DialogUpdate(DialogUpdate::UseRegex(b))),
+            )
+            .spacing(10);
+        let mut col = Column::new()
+            .push(Text::new("Find"))
+            .push(search_input)
+            .push(Text::new("Replace with"))
+            .push(replacement_input)
+            .push(options)
+            .push(Button::new(Text::new("Find")).on_press(search));
+        if let Some(error) = &self.data.error {
+            col = col.push(Text::new(error.clone()));
+        }
+        if self.data.outdated {
+            col = col.push(Text::new("Press Enter or Find to update the matches."));
+        }
+        let all_accepted = self.data.accepted_count() == self.data.matches.len();
+        col = col
+            .push(Text::new(format!("{} matches", self.data.matches.len())))
+            .push(
+                Checkbox::new(all_accepted)
+                    .label("Replace all")
+                    .on_toggle(|b| GuiMessage::DialogUpdate(DialogUpdate::AllMatchesAccepted(b))),
+            );
+        let listed = self.data.matches.iter().take(MAX_LISTED_MATCHES);
+        for (i, text_match) in listed.enumerate() {
+            let location = &self.data.sources[text_match.source].location;
+            let label = format!("{}: {}", location, self.data.context(text_match));
+            col = col.push(
+                Checkbox::new(text_match.accepted)
+                    .label(label)
+                    .on_toggle(move |b| {
+                        GuiMessage::DialogUpdate(DialogUpdate::MatchAccepted(i, b))
+                    }),
+            );
+        }
+        if self.data.matches.len() > MAX_LISTED_MATCHES {
+            col = col.push(Text::new(format!(
+                "{} more matches are not listed and follow \"Replace all\".",
+                self.data.matches.len() - MAX_LISTED_MATCHES
+            )));
+        }
+        let submit_button = Button::new(Text::new(format!(
+            "Replace {} matches",
+            self.data.accepted_count()
+        )))
+        .on_press_maybe((!self.data.outdated).then_some(GuiMessage::DialogSubmit));
+        col.push(submit_button).padding(5).spacing(5).into()
+    }
+
+    /// Typing only marks the matches as outdated, since searching reads every text.
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::SearchText(text) => {
+                self.data.search_text = text;
+                self.data.outdated = true;
+                return;
+            }
+            DialogUpdate::Replacement(text) => {
+                self.data.replacement = text;
+                self.data.outdated = true;
+                return;
+            }
+            DialogUpdate::Search => (),
+            DialogUpdate::CaseSensitive(b) => self.data.case_sensitive = b,
+            DialogUpdate::WholeWord(b) => self.data.whole_word = b,
+            DialogUpdate::UseRegex(b) => self.data.use_regex = b,
+            DialogUpdate::AllMatchesAccepted(b) => {
+                for text_match in self.data.matches.iter_mut() {
+                    text_match.accepted = b;
+                }
+                return;
+            }
+            DialogUpdate::MatchAccepted(i, b) => {
+                if let Some(text_match) = self.data.matches.get_mut(i) {
+                    text_match.accepted = b;
+                }
+                return;
+            }
+            _ => return,
+        }
+        self.data.search();
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::ReplaceMatches(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    fn source(text: &str) -> TextSource {
+        TextSource {
+            location: TextLocation::Description("label".into(), "descriptor".into()),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn options_narrow_down_and_expand_matches() {
+        let mut data = FindReplaceData::new(vec![source("Minas Tirith, minas, Minastir")]);
+        data.search_text = "minas".to_string();
+        data.replacement = "Mundburg".to_string();
+        data.search();
+        assert_eq!(data.matches.len(), 3);
+
+        data.case_sensitive = true;
+        data.search();
+        assert_eq!(data.matches.len(), 1);
+
+        data.case_sensitive = false;
+        data.whole_word = true;
+        data.search();
+        assert_eq!(data.matches.len(), 2);
+
+        data.use_regex = true;
+        data.search_text = r"(\w+) Tirith".to_string();
+        data.replacement = "$1 Anor".to_string();
+        data.search();
+        assert_eq!(data.matches.len(), 1);
+        assert_eq!(data.matches[0].replacement, "Minas Anor");
+
+        data.search_text = "(".to_string();
+        data.search();
+        assert!(data.error.is_some());
+    }
+
+    #[test]
+    fn only_accepted_matches_are_replaced() {
+        let db = example_database();
+        let labels = example_labels();
+        let (descriptor, description) = example_descriptors()[0].clone();
+        let mut data = FindReplaceData::new(read_text_sources(&db).unwrap());
+        data.search_text = description.to_str().trim().to_string();
+        data.replacement = "replaced".to_string();
+        data.case_sensitive = true;
+        data.search();
+        assert_eq!(data.matches.len(), labels.len());
+        data.matches[0].accepted = false;
+        data.write_to_database(&db).unwrap();
+
+        let search_params = EntityColumnSearchParams::new(None, None);
+        let columns = db.read_entity_columns(search_params).unwrap();
+        let descriptions: Vec<&str> = columns
+            .iter()
+            .filter(|col| col.descriptor == descriptor)
+            .map(|col| col.description.to_str())
+            .collect();
+        assert_eq!(
+            descriptions.iter().filter(|d| **d == "replaced\n").count(),
+            2
+        );
+    }
+
+    #[test]
+    fn typing_only_searches_on_request() {
+        let mut dialog = FindReplaceDialog::new(FindReplaceData::new(vec![source("Minas Tirith")]));
+        dialog.update(DialogUpdate::SearchText("minas".to_string()));
+        assert!(dialog.data.matches.is_empty());
+        assert!(dialog
+            .data
+            .clone()
+            .write_to_database(&example_database())
+            .is_err());
+
+        dialog.update(DialogUpdate::Search);
+        assert_eq!(dialog.data.matches.len(), 1);
+        dialog.update(DialogUpdate::AllMatchesAccepted(false));
+        assert_eq!(dialog.data.accepted_count(), 0);
+
+        dialog.update(DialogUpdate::Replacement("Mundburg".to_string()));
+        assert!(dialog.data.outdated);
+        dialog.update(DialogUpdate::WholeWord(true));
+        assert!(!dialog.data.outdated);
+        assert_eq!(dialog.data.matches[0].replacement, "Mundburg");
+    }
+}
@@ -0,0 +1,81 @@
+use std::{collections::HashMap, fmt::Display};
+
+use lorecore::{
+    extractions::extract_labels,
+    sql::{lore_database::LoreDatabase, search_params::EntityColumnSearchParams},
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+/// An entity label offered in a type-ahead picker, matchable by its name as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LabelOption {
+    pub(crate) label: Label,
+    pub(crate) name: Option<String>,
+}
+
+impl Display for LabelOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", self.label, name),
+            None => write!(f, "{}", self.label),
+        }
+    }
+}
+
+/// The labels of all entities, leaving out protected ones that only hold internal data.
+pub(crate) fn read_label_options(db: &LoreDatabase) -> Result<Vec<LabelOption>, LoreGuiError> {
+    let search_params = EntityColumnSearchParams::new(None, None);
+    let entity_columns = db.read_entity_columns(search_params)?;
+    let names: HashMap<&str, &str> = entity_columns
+        .iter()
+        .filter(|col| col.descriptor.to_str() == "_name")
+        .map(|col| (col.label.to_str(), col.description.to_str()))
+        .collect();
+    let options = extract_labels(&entity_columns)
+        .into_iter()
+        .filter(|label| !label.is_protected())
+        .map(|label| {
+            let name = names.get(label.to_str()).map(|name| name.to_string());
+            LabelOption { label, name }
+        })
+        .collect();
+    Ok(options)
+}
+
+pub(crate) fn find_option<'a>(options: &'a [LabelOption], label: &str) -> Option<&'a LabelOption> {
+    options.iter().find(|option| option.label.to_str() == label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialog::new_entity::tests::example_new_entity_data;
+    use crate::tests::example_database;
+
+    #[test]
+    fn label_options_are_matchable_by_name() {
+        let db = example_database();
+        let data = example_new_entity_data();
+        let label = data.label().clone();
+        data.write_to_database(&db).unwrap();
+
+        let options = read_label_options(&db).unwrap();
+        let option = find_option(&options, label.to_str()).unwrap();
+        assert_eq!(option.to_string(), "new_entity (New Entity)");
+    }
+
+    #[test]
+    fn protected_labels_are_not_offered() {
+        let db = example_database();
+        crate::dialog::new_era::tests::example_new_era_data()
+            .write_to_database(&db)
+            .unwrap();
+
+        let options = read_label_options(&db).unwrap();
+        assert!(!options.is_empty());
+        assert!(options.iter().all(|option| !option.label.is_protected()));
+    }
+}
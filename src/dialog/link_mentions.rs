@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use iced::{
+    widget::{Button, Checkbox, Column, Text},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    app::message_handling::GuiMessage, editor::autocomplete::reference,
+    entity_view::backlinks::snippet, errors::LoreGuiError,
+};
+
+use super::{
+    find_replace::{replace_ranges, write_text, TextLocation, TextSource},
+    label_picker::LabelOption,
+    Dialog, DialogUpdate,
+};
+
+/// Shorter labels and names match too many ordinary words to be worth suggesting.
+const MIN_TERM_LENGTH: usize = 3;
+/// How many mentions are listed individually at most.
+const MAX_LISTED_MENTIONS: usize = 50;
+/// The compiled alternation of all terms may grow beyond the regex crate's default limit.
+const MAX_REGEX_SIZE: usize = 1 << 26;
+
+#[derive(Debug, Clone)]
+pub(crate) struct LinkMentionsDialog {
+    data: LinkMentionsData,
+}
+
+impl LinkMentionsDialog {
+    pub(crate) fn new(data: LinkMentionsData) -> Self {
+        LinkMentionsDialog { data }
+    }
+}
+
+/// A plain text occurrence of an entity's label or name.
+#[derive(Debug, Clone)]
+pub(crate) struct Mention {
+    pub(self) source: usize,
+    pub(self) start: usize,
+    pub(self) end: usize,
+    pub(self) label: Label,
+    pub(self) accepted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LinkMentionsData {
+    pub(self) sources: Vec<TextSource>,
+    pub(self) mentions: Vec<Mention>,
+}
+
+impl LinkMentionsData {
+    /// Finds all unlinked mentions of the given entities, accepting every one of them.
+    pub(crate) fn new(sources: Vec<TextSource>, label_options: &[LabelOption]) -> Self {
+        let mut terms: Vec<(&str, &Label)> = vec![];
+        for option in label_options.iter().filter(|o| !o.label.is_protected()) {
+            terms.push((option.label.to_str(), &option.label));
+            if let Some(name) = &option.name {
+                terms.push((name.trim(), &option.label));
+            }
+        }
+        terms.retain(|(term, _)| term.chars().count() >= MIN_TERM_LENGTH);
+        // Longer terms take precedence, so that a name is linked rather than a part of it.
+        terms.sort_by_key(|(term, _)| std::cmp::Reverse(term.len()));
+        let mut labels_by_term: HashMap<String, &Label> = HashMap::new();
+        for (term, label) in terms.iter() {
+            labels_by_term.entry(term.to_lowercase()).or_insert(label);
+        }
+        let regex = match mentions_regex(terms.iter().map(|(term, _)| *term)) {
+            Some(regex) => regex,
+            None => {
+                return LinkMentionsData {
+                    sources,
+                    mentions: vec![],
+                }
+            }
+        };
+
+        let mut mentions = vec![];
+        for (source, text_source) in sources.iter().enumerate() {
+            for captures in regex.captures_iter(&text_source.text) {
+                let found = match captures.name("term") {
+                    Some(found) => found,
+                    None => continue,
+                };
+                let label = match labels_by_term.get(&found.as_str().to_lowercase()) {
+                    Some(label) => *label,
+                    None => continue,
+                };
+                if let TextLocation::Description(own_label, _) = &text_source.location {
+                    if own_label == label {
+                        continue;
+                    }
+                }
+                mentions.push(Mention {
+                    source,
+                    start: found.start(),
+                    end: found.end(),
+                    label: label.clone(),
+                    accepted: true,
+                });
+            }
+        }
+        LinkMentionsData { sources, mentions }
+    }
+
+    fn accepted_count(&self) -> usize {
+        self.mentions.iter().filter(|m| m.accepted).count()
+    }
+
+    /// Turns all accepted mentions into references, writing each changed text once.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        for (index, source) in self.sources.iter().enumerate() {
+            let references: Vec<(usize, usize, String)> = self
+                .mentions
+                .iter()
+                .filter(|m| m.source == index && m.accepted)
+                .map(|m| (m.start, m.end, reference(&m.label)))
+                .collect();
+            if references.is_empty() {
+                continue;
+            }
+            let ranges = references
+                .iter()
+                .map(|(start, end, reference)| (*start, *end, reference.as_str()))
+                .collect();
+            write_text(db, &source.location, &replace_ranges(&source.text, ranges))?;
+        }
+        Ok(())
+    }
+}
+
+/// Existing references are matched as a whole, so that mentions within them are skipped.
+fn mentions_regex<'a>(terms: impl Iterator<Item = &'a str>) -> Option<Regex> {
+    let alternatives: Vec<String> = terms.map(regex::escape).collect();
+    if alternatives.is_empty() {
+        return None;
+    }
+    let pattern = format!(
+        r"\\reference\{{[^}}]*\}}|\b(?P<term>{})\b",
+        alternatives.join("|")
+    );
+    RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .size_limit(MAX_REGEX_SIZE)
+        .build()
+        .ok()
+}
+
+impl Dialog for LinkMentionsDialog {
+    fn header(&self) -> String {
+        "Link mentions of entities".to_string()
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let all_accepted = self.data.accepted_count() == self.data.mentions.len();
+        let mut col = Column::new()
+            .push(Text::new(format!(
+                "{} unlinked mentions",
+                self.data.mentions.len()
+            )))
+            .push(
+                Checkbox::new(all_accepted)
+                    .label("Link all")
+                    .on_toggle(|b| GuiMessage::DialogUpdate(DialogUpdate::AllMatchesAccepted(b))),
+            );
+        let listed = self.data.mentions.iter().take(MAX_LISTED_MENTIONS);
+        for (i, mention) in listed.enumerate() {
+            let source = &self.data.sources[mention.source];
+            let label = format!(
+                "{}: {} -> {}",
+                source.location,
+                snippet(&source.text, (mention.start, mention.end)),
+                mention.label
+            );
+            col = col.push(
+                Checkbox::new(mention.accepted)
+                    .label(label)
+                    .on_toggle(move |b| {
+                        GuiMessage::DialogUpdate(DialogUpdate::MatchAccepted(i, b))
+                    }),
+            );
+        }
+        if self.data.mentions.len() > MAX_LISTED_MENTIONS {
+            col = col.push(Text::new(format!(
+                "{} more mentions are not listed and follow \"Link all\".",
+                self.data.mentions.len() - MAX_LISTED_MENTIONS
+            )));
+        }
+        let submit_button = Button::new(Text::new(format!(
+            "Link {} mentions",
+            self.data.accepted_count()
+        )))
+        .on_press(GuiMessage::DialogSubmit);
+        col.push(submit_button).padding(5).spacing(5).into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::AllMatchesAccepted(b) => {
+                for mention in self.data.mentions.iter_mut() {
+                    mention.accepted = b;
+                }
+            }
+            DialogUpdate::MatchAccepted(i, b) => {
+                if let Some(mention) = self.data.mentions.get_mut(i) {
+                    mention.accepted = b;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::LinkMentions(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(label: &str, text: &str) -> TextSource {
+        TextSource {
+            location: TextLocation::Description(label.into(), "descriptor".into()),
+            text: text.to_string(),
+        }
+    }
+
+    fn option(label: &str, name: &str) -> LabelOption {
+        LabelOption {
+            label: label.into(),
+            name: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn only_unlinked_mentions_of_other_entities_are_suggested() {
+        let options = vec![
+            option("frodo", "Frodo Baggins"),
+            option("bilbo", "Bilbo Baggins"),
+        ];
+        let sources = vec![
+            source("frodo", "Frodo Baggins, nephew of Bilbo."),
+            source(
+                "sam",
+                "Serves Frodo Baggins and \\reference{bilbo}, unlike Bilbo Baggins.",
+            ),
+        ];
+        let data = LinkMentionsData::new(sources, &options);
+
+        let mentioned: Vec<(usize, &str)> = data
+            .mentions
+            .iter()
+            .map(|m| (m.source, m.label.to_str()))
+            .collect();
+        assert_eq!(mentioned, vec![(0, "bilbo"), (1, "frodo"), (1, "bilbo")]);
+
+        let sam = &data.sources[1];
+        let ranges = data
+            .mentions
+            .iter()
+            .filter(|m| m.source == 1)
+            .map(|m| (m.start, m.end, "X"))
+            .collect();
+        assert_eq!(
+            replace_ranges(&sam.text, ranges),
+            "Serves X and \\reference{bilbo}, unlike X."
+        );
+    }
+}
@@ -0,0 +1,603 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use iced::{
+    widget::{combo_box, Button, Column, PickList, Row, Text},
+    Alignment, Element, Length,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{
+            EntityColumnSearchParams, HistoryItemSearchParams, RelationshipSearchParams,
+            SqlSearchText,
+        },
+    },
+    types::*,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    editor::autocomplete::reference,
+    errors::LoreGuiError,
+    history_view::properties::{
+        change_history_item_properties, get_participants, with_participants,
+    },
+};
+
+use super::{
+    find_replace::{read_text_sources, replace_ranges, write_text, TextLocation},
+    label_picker::{find_option, LabelOption},
+    Dialog, DialogUpdate,
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct MergeEntitiesDialog {
+    picker: combo_box::State<LabelOption>,
+    columns: Vec<EntityColumn>,
+    data: MergeEntitiesData,
+}
+
+impl MergeEntitiesDialog {
+    /// The columns of all entities are needed to list the conflicts for whichever label is picked.
+    pub(crate) fn new(
+        data: MergeEntitiesData,
+        labels: Vec<LabelOption>,
+        columns: Vec<EntityColumn>,
+    ) -> Self {
+        MergeEntitiesDialog {
+            picker: combo_box::State::new(labels),
+            columns,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum MergeResolution {
+    #[default]
+    KeepLeft,
+    KeepRight,
+    Concatenate,
+}
+
+impl MergeResolution {
+    const ALL: [MergeResolution; 3] = [
+        MergeResolution::KeepLeft,
+        MergeResolution::KeepRight,
+        MergeResolution::Concatenate,
+    ];
+}
+
+impl Display for MergeResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeResolution::KeepLeft => write!(f, "Keep left"),
+            MergeResolution::KeepRight => write!(f, "Keep right"),
+            MergeResolution::Concatenate => write!(f, "Concatenate"),
+        }
+    }
+}
+
+/// A descriptor both entities have, with different descriptions.
+#[derive(Debug, Clone)]
+pub(crate) struct DescriptorConflict {
+    pub(crate) descriptor: Descriptor,
+    pub(crate) left: Description,
+    pub(crate) right: Description,
+    pub(crate) resolution: MergeResolution,
+}
+
+impl DescriptorConflict {
+    fn resolved(&self) -> Description {
+        match self.resolution {
+            MergeResolution::KeepLeft => self.left.clone(),
+            MergeResolution::KeepRight => self.right.clone(),
+            MergeResolution::Concatenate => format!(
+                "{}\n\n{}",
+                self.left.to_str().trim_end(),
+                self.right.to_str()
+            )
+            .into(),
+        }
+    }
+}
+
+fn find_conflicts(
+    columns: &[EntityColumn],
+    left: &Label,
+    right: &Label,
+) -> Vec<DescriptorConflict> {
+    let right_descriptions: HashMap<&str, &Description> = columns
+        .iter()
+        .filter(|col| &col.label == right)
+        .map(|col| (col.descriptor.to_str(), &col.description))
+        .collect();
+    let mut conflicts = vec![];
+    for left_col in columns.iter().filter(|col| &col.label == left) {
+        if let Some(right_description) = right_descriptions.get(left_col.descriptor.to_str()) {
+            if *right_description != &left_col.description {
+                conflicts.push(DescriptorConflict {
+                    descriptor: left_col.descriptor.clone(),
+                    left: left_col.description.clone(),
+                    right: (*right_description).clone(),
+                    resolution: MergeResolution::default(),
+                });
+            }
+        }
+    }
+    conflicts.sort_by(|a, b| a.descriptor.to_str().cmp(b.descriptor.to_str()));
+    conflicts
+}
+
+/// Everything a merge writes and deletes, read before anything is changed.
+struct MergePlan {
+    other: Label,
+    new_columns: Vec<EntityColumn>,
+    changed_descriptions: Vec<(Descriptor, Description)>,
+    old_relationships: Vec<EntityRelationship>,
+    new_relationships: Vec<EntityRelationship>,
+    history_items: Vec<(Timestamp, HistoryItemProperties)>,
+    texts: Vec<(TextLocation, String)>,
+    rewired_references: usize,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MergeEntitiesData {
+    pub(self) left: Label,
+    pub(self) right: Label,
+    pub(self) survivor: Label,
+    pub(self) conflicts: Vec<DescriptorConflict>,
+}
+
+impl MergeEntitiesData {
+    pub(crate) fn new(left: Label) -> Self {
+        MergeEntitiesData {
+            survivor: left.clone(),
+            left,
+            right: "".into(),
+            conflicts: vec![],
+        }
+    }
+
+    fn other(&self) -> &Label {
+        if self.survivor == self.left {
+            &self.right
+        } else {
+            &self.left
+        }
+    }
+
+    fn plan(&self, db: &LoreDatabase) -> Result<MergePlan, LoreGuiError> {
+        if self.right.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Cannot merge without a second entity.".to_string(),
+            ));
+        }
+        if self.left == self.right {
+            return Err(LoreGuiError::InputError(
+                "Cannot merge an entity with itself.".to_string(),
+            ));
+        }
+        let other = self.other().clone();
+        let read_columns = |label: &Label| {
+            let search_params =
+                EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+            db.read_entity_columns(search_params)
+        };
+        let survivor_columns = read_columns(&self.survivor)?;
+        let other_columns = read_columns(&other)?;
+        if survivor_columns.is_empty() || other_columns.is_empty() {
+            return Err(LoreGuiError::InputError(format!(
+                "Both {} and {} need to exist to be merged.",
+                self.left, self.right
+            )));
+        }
+
+        let (mut new_columns, mut changed_descriptions) =
+            self.merge_descriptions(&survivor_columns, other_columns);
+        let (old_relationships, new_relationships) =
+            rewire_relationships(db, &self.survivor, &other)?;
+        let history_items = rewire_history_items(db, &self.survivor, &other)?;
+
+        let mut rewired_references = 0;
+        let mut rewire = |text: &str| {
+            let (text, count) = rewire_references(text, &self.survivor, &other);
+            rewired_references += count;
+            text
+        };
+        for col in new_columns.iter_mut() {
+            col.description = rewire(col.description.to_str()).as_str().into();
+        }
+        for (_, description) in changed_descriptions.iter_mut() {
+            *description = rewire(description.to_str()).as_str().into();
+        }
+        let mut texts = vec![];
+        for source in read_text_sources(db)? {
+            // The other entity is deleted, and changed descriptions are rewired above.
+            if let TextLocation::Description(label, descriptor) = &source.location {
+                let is_changed = changed_descriptions.iter().any(|(d, _)| d == descriptor);
+                if label == &other || (label == &self.survivor && is_changed) {
+                    continue;
+                }
+            }
+            let text = rewire(&source.text);
+            if text != source.text {
+                texts.push((source.location, text));
+            }
+        }
+        Ok(MergePlan {
+            other,
+            new_columns,
+            changed_descriptions,
+            old_relationships,
+            new_relationships,
+            history_items,
+            texts,
+            rewired_references,
+        })
+    }
+
+    /// What the merge is going to change, to be confirmed before it is written.
+    pub(crate) fn summary(&self, db: &LoreDatabase) -> Result<String, LoreGuiError> {
+        let plan = self.plan(db)?;
+        Ok(format!(
+            "Do you really want to merge {} into {}? This moves {} descriptors, changes {} descriptions, rewires {} relationships and {} references and updates {} history items. Afterwards {} is deleted.",
+            plan.other,
+            self.survivor,
+            plan.new_columns.len(),
+            plan.changed_descriptions.len(),
+            plan.old_relationships.len(),
+            plan.rewired_references,
+            plan.history_items.len(),
+            plan.other
+        ))
+    }
+
+    /// All new rows are written before any old one is deleted.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        let plan = self.plan(db)?;
+        if !plan.new_columns.is_empty() {
+            db.write_entity_columns(plan.new_columns)?;
+        }
+        for (descriptor, description) in plan.changed_descriptions {
+            db.change_entity_description((&self.survivor, &descriptor), &description)?;
+        }
+        if !plan.new_relationships.is_empty() {
+            db.write_relationships(plan.new_relationships)?;
+        }
+        for (location, text) in plan.texts {
+            write_text(db, &location, &text)?;
+        }
+        for (timestamp, properties) in plan.history_items {
+            change_history_item_properties(db, timestamp, properties)?;
+        }
+        for rel in plan.old_relationships {
+            db.delete_relationship(rel)?;
+        }
+        db.delete_entity(plan.other)?;
+        Ok(())
+    }
+
+    /// The columns the survivor is missing, and the descriptions that change due to a conflict.
+    fn merge_descriptions(
+        &self,
+        survivor_columns: &[EntityColumn],
+        other_columns: Vec<EntityColumn>,
+    ) -> (Vec<EntityColumn>, Vec<(Descriptor, Description)>) {
+        let survivor_descriptions: HashMap<&str, &Description> = survivor_columns
+            .iter()
+            .map(|col| (col.descriptor.to_str(), &col.description))
+            .collect();
+        let conflicts: HashMap<&str, &DescriptorConflict> = self
+            .conflicts
+            .iter()
+            .map(|c| (c.descriptor.to_str(), c))
+            .collect();
+        let mut missing = vec![];
+        let mut changed = vec![];
+        for other_col in other_columns {
+            let descriptor = other_col.descriptor.to_str();
+            let survivor_description = match survivor_descriptions.get(descriptor) {
+                Some(description) => *description,
+                None => {
+                    missing.push(EntityColumn {
+                        label: self.survivor.clone(),
+                        ..other_col
+                    });
+                    continue;
+                }
+            };
+            if let Some(conflict) = conflicts.get(descriptor) {
+                let description = conflict.resolved();
+                if &description != survivor_description {
+                    changed.push((other_col.descriptor.clone(), description));
+                }
+            }
+        }
+        (missing, changed)
+    }
+
+    pub(crate) fn survivor(&self) -> &Label {
+        &self.survivor
+    }
+}
+
+/// Relationships between the two entities would point at the survivor itself and are dropped.
+fn rewire_relationships(
+    db: &LoreDatabase,
+    survivor: &Label,
+    other: &Label,
+) -> Result<(Vec<EntityRelationship>, Vec<EntityRelationship>), LoreGuiError> {
+    let involving = |label: &Label| -> Result<Vec<EntityRelationship>, LoreGuiError> {
+        let as_parent =
+            RelationshipSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+        let as_child =
+            RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label.to_str())));
+        let mut relationships = db.read_relationships(as_parent)?;
+        let incoming = db.read_relationships(as_child)?;
+        relationships.extend(
+            incoming
+                .into_iter()
+                .filter(|rel| rel.parent.to_str() != label.to_str()),
+        );
+        Ok(relationships)
+    };
+    let key = |rel: &EntityRelationship| {
+        (
+            rel.parent.to_str().to_string(),
+            rel.child.to_str().to_string(),
+            rel.role.to_str().to_string(),
+        )
+    };
+    let mut existing: HashSet<_> = involving(survivor)?.iter().map(key).collect();
+    let replace = |end: &str| {
+        if end == other.to_str() {
+            survivor.to_str().to_string()
+        } else {
+            end.to_string()
+        }
+    };
+
+    let old_relationships = involving(other)?;
+    let mut rewired = vec![];
+    for rel in old_relationships.iter() {
+        let new_rel = EntityRelationship {
+            parent: replace(rel.parent.to_str()).as_str().into(),
+            child: replace(rel.child.to_str()).as_str().into(),
+            role: rel.role.clone(),
+        };
+        let is_self_reference = new_rel.parent.to_str() == new_rel.child.to_str();
+        if !is_self_reference && existing.insert(key(&new_rel)) {
+            rewired.push(new_rel);
+        }
+    }
+    Ok((old_relationships, rewired))
+}
+
+/// Also returns the number of references changed.
+fn rewire_references(text: &str, survivor: &Label, other: &Label) -> (String, usize) {
+    let old_reference = reference(other);
+    let new_reference = reference(survivor);
+    let ranges: Vec<(usize, usize, &str)> = text
+        .match_indices(&old_reference)
+        .map(|(start, found)| (start, start + found.len(), new_reference.as_str()))
+        .collect();
+    let count = ranges.len();
+    (replace_ranges(text, ranges), count)
+}
+
+/// The new properties of every history item the other entity participates in.
+fn rewire_history_items(
+    db: &LoreDatabase,
+    survivor: &Label,
+    other: &Label,
+) -> Result<Vec<(Timestamp, HistoryItemProperties)>, LoreGuiError> {
+    let search_params = HistoryItemSearchParams::new(None, None, None, None);
+    let mut changes = vec![];
+    for item in db.read_history_items(search_params)? {
+        let participants = get_participants(&item.properties);
+        if !participants.contains(other) {
+            continue;
+        }
+        let mut rewired: Vec<Label> = vec![];
+        for participant in participants {
+            let participant = if &participant == other {
+                survivor.clone()
+            } else {
+                participant
+            };
+            if !rewired.contains(&participant) {
+                rewired.push(participant);
+            }
+        }
+        let properties = with_participants(&item.properties, &rewired);
+        changes.push((item.timestamp, properties));
+    }
+    Ok(changes)
+}
+
+impl Dialog for MergeEntitiesDialog {
+    fn header(&self) -> String {
+        format!("Merge entity: {}", self.data.left)
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let selected = find_option(self.picker.options(), self.data.right.to_str());
+        let right_input = combo_box(
+            &self.picker,
+            "Type to search labels and names...",
+            selected,
+            |o| GuiMessage::DialogUpdate(DialogUpdate::Label(o.label)),
+        );
+        let mut col = Column::new()
+            .push(Text::new(format!(
+                "Merge {} (left) with (right):",
+                self.data.left
+            )))
+            .push(right_input);
+        if !self.data.right.to_str().is_empty() {
+            let survivor_input = PickList::new(
+                vec![self.data.left.clone(), self.data.right.clone()],
+                Some(self.data.survivor.clone()),
+                |l| GuiMessage::DialogUpdate(DialogUpdate::Survivor(l)),
+            );
+            col = col
+                .push(Text::new("Surviving label:"))
+                .push(survivor_input)
+                .push(Text::new(format!(
+                    "{} will be deleted after the merge.",
+                    self.data.other()
+                )));
+        }
+        for conflict in self.data.conflicts.iter() {
+            let descriptor = conflict.descriptor.clone();
+            let resolution_input =
+                PickList::new(MergeResolution::ALL, Some(conflict.resolution), move |r| {
+                    GuiMessage::DialogUpdate(DialogUpdate::Resolution(descriptor.clone(), r))
+                });
+            let descriptions = Row::new()
+                .push(Text::new(conflict.left.to_str()).width(Length::Fill))
+                .push(Text::new(conflict.right.to_str()).width(Length::Fill))
+                .spacing(10);
+            col = col
+                .push(
+                    Row::new()
+                        .push(Text::new(conflict.descriptor.to_str()))
+                        .push(resolution_input)
+                        .align_y(Alignment::Center)
+                        .spacing(5),
+                )
+                .push(descriptions);
+        }
+        let submit_button = Button::new(Text::new("Merge")).on_press(GuiMessage::DialogSubmit);
+        col.push(submit_button).padding(5).spacing(5).into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Label(right) => {
+                self.data.conflicts = find_conflicts(&self.columns, &self.data.left, &right);
+                self.data.survivor = self.data.left.clone();
+                self.data.right = right;
+            }
+            DialogUpdate::Survivor(survivor) => self.data.survivor = survivor,
+            DialogUpdate::Resolution(descriptor, resolution) => {
+                let conflict = self
+                    .data
+                    .conflicts
+                    .iter_mut()
+                    .find(|c| c.descriptor == descriptor);
+                if let Some(conflict) = conflict {
+                    conflict.resolution = resolution;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::ConfirmMergeEntities(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    #[test]
+    fn merging_resolves_conflicts_and_rewires_relationships() {
+        let db = example_database();
+        let labels = example_labels();
+        let descriptors = example_descriptors();
+        let changed: Description = "A different description\n".into();
+        db.change_entity_description((&labels[1], &descriptors[0].0), &changed)
+            .unwrap();
+        let columns = db
+            .read_entity_columns(EntityColumnSearchParams::new(None, None))
+            .unwrap();
+
+        let data = MergeEntitiesData::new(labels[0].clone());
+        let mut dialog = MergeEntitiesDialog::new(data, vec![], columns);
+        dialog.update(DialogUpdate::Label(labels[1].clone()));
+        assert_eq!(dialog.data.conflicts.len(), 1);
+        dialog.update(DialogUpdate::Resolution(
+            descriptors[0].0.clone(),
+            MergeResolution::Concatenate,
+        ));
+        let summary = dialog.data.summary(&db).unwrap();
+        assert!(summary.contains("changes 1 descriptions"));
+        dialog.data.clone().write_to_database(&db).unwrap();
+
+        let search = |label: &Label| {
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None)
+        };
+        assert!(db
+            .read_entity_columns(search(&labels[1]))
+            .unwrap()
+            .is_empty());
+        let survivor_columns = db.read_entity_columns(search(&labels[0])).unwrap();
+        let merged = survivor_columns
+            .iter()
+            .find(|col| col.descriptor == descriptors[0].0)
+            .unwrap();
+        assert_eq!(
+            merged.description.to_str(),
+            format!(
+                "{}\n\n{}",
+                descriptors[0].1.to_str().trim_end(),
+                changed.to_str()
+            )
+        );
+
+        let all = db
+            .read_relationships(RelationshipSearchParams::new(None, None))
+            .unwrap();
+        assert!(all
+            .iter()
+            .all(|rel| rel.parent.to_str() != labels[1].to_str()
+                && rel.child.to_str() != labels[1].to_str()));
+        let between_survivor_and_third = all
+            .iter()
+            .filter(|rel| {
+                rel.parent.to_str() == labels[2].to_str()
+                    && rel.child.to_str() == labels[0].to_str()
+            })
+            .count();
+        assert_eq!(between_survivor_and_third, 2);
+    }
+
+    #[test]
+    fn references_to_the_merged_entity_point_at_the_survivor() {
+        let db = example_database();
+        let labels = example_labels();
+        let descriptor: Descriptor = "Mention".into();
+        let mention: Description = format!("Knows {}.", reference(&labels[1])).into();
+        db.write_entity_columns(vec![EntityColumn {
+            label: labels[2].clone(),
+            descriptor: descriptor.clone(),
+            description: mention,
+        }])
+        .unwrap();
+
+        let mut data = MergeEntitiesData::new(labels[0].clone());
+        data.right = labels[1].clone();
+        let summary = data.summary(&db).unwrap();
+        assert!(summary.contains("and 1 references"));
+        data.write_to_database(&db).unwrap();
+
+        let search_params = EntityColumnSearchParams::new(
+            Some(SqlSearchText::exact(labels[2].to_str())),
+            Some(SqlSearchText::exact(descriptor.to_str())),
+        );
+        let columns = db.read_entity_columns(search_params).unwrap();
+        assert_eq!(
+            columns[0].description.to_str(),
+            format!("Knows {}.", reference(&labels[0]))
+        );
+    }
+}
@@ -6,16 +6,35 @@ use iced::{
 use iced_aw::{style, Card};
 use lorecore::{errors::LoreCoreError, types::*};
 
+use crate::{
+    dialog::{
+        merge_entities::MergeResolution, rename_descriptor_everywhere::RenameConflictHandling,
+    },
+    history_view::dating::DatePrecision,
+};
+
+pub(crate) mod change_category;
 pub(crate) mod change_role;
 pub(crate) mod confirmation;
+pub(crate) mod duplicate_entity;
 pub(crate) mod error;
+pub(crate) mod find_replace;
+pub(crate) mod label_picker;
+pub(crate) mod link_mentions;
+pub(crate) mod merge_entities;
 pub(crate) mod new_descriptor;
 pub(crate) mod new_entity;
+pub(crate) mod new_era;
 pub(crate) mod new_history_item;
+pub(crate) mod new_inverse_role_pair;
 pub(crate) mod new_relationship;
 pub(crate) mod redate_history;
+pub(crate) mod redate_history_items;
 pub(crate) mod relabel_entity;
 pub(crate) mod rename_descriptor;
+pub(crate) mod rename_descriptor_everywhere;
+pub(crate) mod rename_role;
+pub(crate) mod retarget_relationship;
 
 pub(crate) enum CardStyle {
     Primary,
@@ -53,15 +72,33 @@ pub(crate) trait Dialog {
 
 #[derive(Debug, Clone)]
 pub(crate) enum DialogUpdate {
+    AllMatchesAccepted(bool),
+    CaseSensitive(bool),
     Category(String),
     Child(Child),
+    Circa(bool),
+    ConflictHandling(RenameConflictHandling),
     Content(HistoryItemContent),
     Day(Result<Day, LoreCoreError>),
     Description(Description),
     Descriptor(Descriptor),
+    EndYear(Result<Year, LoreCoreError>),
+    IncludeRelationships(bool),
+    InverseRole(Role),
     Label(Label),
+    MatchAccepted(usize, bool),
     Name(String),
+    OptionalDay(Result<Option<Day>, LoreCoreError>),
     Parent(Parent),
+    Precision(DatePrecision),
+    RangeEnd(Result<Option<Year>, LoreCoreError>),
+    Replacement(String),
+    Resolution(Descriptor, MergeResolution),
     Role(Role),
+    Search,
+    SearchText(String),
+    Survivor(Label),
+    UseRegex(bool),
+    WholeWord(bool),
     Year(Result<Year, LoreCoreError>),
 }
@@ -20,6 +20,10 @@ impl NewEntityDialog {
             data: NewEntityData::new(),
         }
     }
+
+    pub(crate) fn with_data(data: NewEntityData) -> Self {
+        NewEntityDialog { data }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -38,6 +42,29 @@ impl NewEntityData {
         }
     }
 
+    /// A placeholder entity for a label that is referenced but was never created.
+    pub(crate) fn stub(label: Label) -> Self {
+        NewEntityData {
+            name: label.to_str().to_string(),
+            label,
+            category: "stub".to_string(),
+        }
+    }
+
+    /// An entity named after a term found in the texts, with a label derived from it.
+    pub(crate) fn from_term(term: &str) -> Self {
+        let label: String = term
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        NewEntityData {
+            label: label.as_str().into(),
+            name: term.to_string(),
+            category: String::new(),
+        }
+    }
+
     pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
         if self.label.to_str().is_empty() {
             return Err(LoreGuiError::InputError(
@@ -0,0 +1,138 @@
+use iced::{
+    widget::{Button, Column, Text, TextInput},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    history_view::era::{read_eras, Era},
+};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewEraDialog {
+    data: NewEraData,
+}
+
+impl NewEraDialog {
+    pub(crate) fn new() -> Self {
+        NewEraDialog {
+            data: NewEraData::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewEraData {
+    pub(self) name: String,
+    pub(self) start: Year,
+    pub(self) end: Year,
+}
+
+impl NewEraData {
+    pub(crate) fn new() -> Self {
+        NewEraData {
+            name: String::new(),
+            start: 0.into(),
+            end: 0.into(),
+        }
+    }
+
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.name.is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Cannot create era with empty name.".to_string(),
+            ));
+        }
+        if self.start.to_int() > self.end.to_int() {
+            return Err(LoreGuiError::InputError(
+                "An era cannot end before it starts.".to_string(),
+            ));
+        }
+        if read_eras(db)?.iter().any(|era| era.name == self.name) {
+            return Err(LoreGuiError::InputError(format!(
+                "An era named {} already exists.",
+                self.name
+            )));
+        }
+
+        let era = Era {
+            name: self.name,
+            start: self.start,
+            end: self.end,
+        };
+        db.write_entity_columns(vec![era.to_column()])?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Dialog for NewEraDialog {
+    fn header(&self) -> String {
+        "Create new era".to_string()
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let name_input = TextInput::new("", &self.data.name)
+            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Name(s)));
+        let start_input = TextInput::new("", &self.data.start.to_string())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Year(i.try_into())));
+        let end_input = TextInput::new("", &self.data.end.to_string())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::EndYear(i.try_into())));
+        let submit_button = Button::new(Text::new("Create")).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new("Name:"))
+            .push(name_input)
+            .push(Text::new("First Year:"))
+            .push(start_input)
+            .push(Text::new("Last Year:"))
+            .push(end_input)
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Name(name) => self.data.name = name,
+            DialogUpdate::Year(Ok(year)) => self.data.start = year,
+            DialogUpdate::EndYear(Ok(year)) => self.data.end = year,
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::NewEra(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn example_new_era_data() -> NewEraData {
+        NewEraData {
+            name: "Age of Example".to_string(),
+            start: (-22).into(),
+            end: 0.into(),
+        }
+    }
+
+    #[test]
+    fn era_names_must_be_unique() {
+        let db = crate::tests::example_database();
+        example_new_era_data().write_to_database(&db).unwrap();
+        let mut duplicate = example_new_era_data();
+        duplicate.start = (-500).into();
+        assert!(duplicate.write_to_database(&db).is_err());
+        assert_eq!(read_eras(&db).unwrap()[0].start.to_int(), -22);
+    }
+}
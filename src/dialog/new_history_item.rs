@@ -1,10 +1,17 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Checkbox, Column, PickList, Text, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, timestamp::current_timestamp, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    history_view::{
+        dating::{DatePrecision, Dating},
+        properties::with_dating,
+    },
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -21,6 +28,7 @@ impl NewHistoryDialog {
                 day: Day::NONE,
                 content: "".into(),
                 properties: HistoryItemProperties::none(),
+                dating: Dating::default(),
                 timestamp: current_timestamp(),
             },
         }
@@ -34,16 +42,18 @@ pub(crate) struct NewHistoryData {
     pub(self) day: Day,
     pub(self) content: HistoryItemContent,
     pub(self) properties: HistoryItemProperties,
+    pub(self) dating: Dating,
 }
 
 impl NewHistoryData {
     pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        self.dating.validate(&self.year)?;
         let item = HistoryItem {
             timestamp: self.timestamp,
             year: self.year,
             day: self.day,
             content: self.content,
-            properties: self.properties,
+            properties: with_dating(&self.properties, &self.dating),
         };
         db.write_history_items(vec![item])?;
         Ok(())
@@ -86,6 +96,7 @@ impl Dialog for NewHistoryDialog {
             .push(year_input)
             .push(Text::new("Day:"))
             .push(day_input)
+            .push(dating_inputs(&self.data.dating))
             .push(Text::new("Content:"))
             .push(content_input)
             .push(submit_button)
@@ -99,6 +110,9 @@ impl Dialog for NewHistoryDialog {
             DialogUpdate::Year(Ok(year)) => self.data.year = year,
             DialogUpdate::Day(Ok(day)) => self.data.day = day,
             DialogUpdate::Content(content) => self.data.content = content,
+            DialogUpdate::Circa(circa) => self.data.dating.circa = circa,
+            DialogUpdate::RangeEnd(Ok(end)) => self.data.dating.range_end = end,
+            DialogUpdate::Precision(precision) => self.data.dating.precision = precision,
             _ => (),
         }
     }
@@ -108,6 +122,34 @@ impl Dialog for NewHistoryDialog {
     }
 }
 
+pub(super) fn dating_inputs(dating: &Dating) -> Column<'_, GuiMessage> {
+    let circa_input = Checkbox::new(dating.circa)
+        .label("Circa")
+        .on_toggle(|c| GuiMessage::DialogUpdate(DialogUpdate::Circa(c)));
+    let range_end_string = dating
+        .range_end
+        .map(|end| end.to_string())
+        .unwrap_or_default();
+    let range_end_input = TextInput::new("", &range_end_string).on_input(|i| {
+        let end = if i.is_empty() {
+            Ok(None)
+        } else {
+            Year::try_from(i).map(Some)
+        };
+        GuiMessage::DialogUpdate(DialogUpdate::RangeEnd(end))
+    });
+    let precision_input = PickList::new(DatePrecision::ALL, Some(dating.precision), |p| {
+        GuiMessage::DialogUpdate(DialogUpdate::Precision(p))
+    });
+    Column::new()
+        .push(circa_input)
+        .push(Text::new("Until Year (optional):"))
+        .push(range_end_input)
+        .push(Text::new("Precision:"))
+        .push(precision_input)
+        .spacing(5)
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use lorecore::timestamp::current_timestamp;
@@ -131,7 +173,23 @@ pub(crate) mod tests {
             day,
             content,
             properties,
+            dating: Dating::default(),
             timestamp,
         }
     }
+
+    pub(crate) fn example_ranged_history_data() -> NewHistoryData {
+        NewHistoryData {
+            year: (-30).into(),
+            day: Day::NONE,
+            content: HistoryItemContent::from("Ranged content\n"),
+            properties: HistoryItemProperties::none(),
+            dating: Dating {
+                circa: false,
+                range_end: Some((-10).into()),
+                precision: DatePrecision::Exact,
+            },
+            timestamp: current_timestamp(),
+        }
+    }
 }
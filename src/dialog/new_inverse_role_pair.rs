@@ -0,0 +1,112 @@
+use iced::{
+    widget::{Button, Column, Text, TextInput},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    relationship_view::inverse_roles::{read_inverse_role_pairs, InverseRolePair},
+};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewInverseRolePairDialog {
+    data: NewInverseRolePairData,
+}
+
+impl NewInverseRolePairDialog {
+    pub(crate) fn new() -> Self {
+        NewInverseRolePairDialog {
+            data: NewInverseRolePairData::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewInverseRolePairData {
+    pub(self) role: Role,
+    pub(self) inverse: Role,
+}
+
+impl NewInverseRolePairData {
+    pub(crate) fn new() -> Self {
+        NewInverseRolePairData {
+            role: String::new().into(),
+            inverse: String::new().into(),
+        }
+    }
+
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.role.to_str().is_empty() || self.inverse.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Role and inverse role cannot be empty.".to_string(),
+            ));
+        }
+        let pairs = read_inverse_role_pairs(db)?;
+        for role in [&self.role, &self.inverse] {
+            if pairs.iter().any(|pair| pair.involves(role)) {
+                return Err(LoreGuiError::InputError(format!(
+                    "Role {} already has an inverse.",
+                    role
+                )));
+            }
+        }
+
+        let pair = InverseRolePair {
+            role: self.role,
+            inverse: self.inverse,
+        };
+        db.write_entity_columns(vec![pair.to_column()])?;
+        Ok(())
+    }
+}
+
+impl Dialog for NewInverseRolePairDialog {
+    fn header(&self) -> String {
+        "Create new inverse role pair".to_string()
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let role_input = TextInput::new("", self.data.role.to_str())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())));
+        let inverse_input = TextInput::new("", self.data.inverse.to_str())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::InverseRole(i.into())));
+        let submit_button = Button::new(Text::new("Create")).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new("Role:"))
+            .push(role_input)
+            .push(Text::new("Inverse Role:"))
+            .push(inverse_input)
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Role(role) => self.data.role = role,
+            DialogUpdate::InverseRole(inverse) => self.data.inverse = inverse,
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::NewInverseRolePair(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn example_new_inverse_role_pair_data() -> NewInverseRolePairData {
+        NewInverseRolePairData {
+            role: "mentor".into(),
+            inverse: "apprentice".into(),
+        }
+    }
+}
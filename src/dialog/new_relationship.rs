@@ -1,28 +1,38 @@
 use iced::{
-    widget::{Button, Column, PickList, Text, TextInput},
+    widget::{combo_box, Button, Column, Text},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
 use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
 
-use super::{Dialog, DialogUpdate};
+use super::{
+    label_picker::{find_option, LabelOption},
+    Dialog, DialogUpdate,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct NewRelationshipDialog {
-    parent_labels: Vec<Parent>,
-    child_labels: Vec<Child>,
+    parent_picker: combo_box::State<LabelOption>,
+    child_picker: combo_box::State<LabelOption>,
+    role_picker: combo_box::State<Role>,
     data: NewRelationshipData,
 }
 
 impl NewRelationshipDialog {
-    pub(crate) fn new(parent_labels: Vec<Parent>, child_labels: Vec<Child>) -> Self {
+    pub(crate) fn new(labels: Vec<LabelOption>, roles: Vec<Role>) -> Self {
         NewRelationshipDialog {
-            parent_labels,
-            child_labels,
+            parent_picker: combo_box::State::new(labels.clone()),
+            child_picker: combo_box::State::new(labels),
+            role_picker: combo_box::State::new(roles),
             data: NewRelationshipData::new(),
         }
     }
+
+    pub(crate) fn with_parent(mut self, parent: Parent) -> Self {
+        self.data.parent = parent;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -70,30 +80,43 @@ impl NewRelationshipData {
     }
 }
 
+impl From<EntityRelationship> for NewRelationshipData {
+    fn from(rel: EntityRelationship) -> Self {
+        NewRelationshipData {
+            parent: rel.parent,
+            child: rel.child,
+            role: rel.role,
+        }
+    }
+}
+
 impl Dialog for NewRelationshipDialog {
     fn header(&self) -> String {
         "New Relationship".to_string()
     }
 
     fn body(&self) -> Element<'_, GuiMessage> {
-        let selected_parent = if self.data.parent.to_str().is_empty() {
-            None
-        } else {
-            Some(self.data.parent.clone())
-        };
-        let parent_input = PickList::new(self.parent_labels.clone(), selected_parent, |s| {
-            GuiMessage::DialogUpdate(DialogUpdate::Parent(s))
-        });
-        let selected_child = if self.data.child.to_str().is_empty() {
-            None
-        } else {
-            Some(self.data.child.clone())
-        };
-        let child_input = PickList::new(self.child_labels.clone(), selected_child, |s| {
-            GuiMessage::DialogUpdate(DialogUpdate::Child(s))
-        });
-        let role_input = TextInput::new("", self.data.role.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())));
+        let selected_parent = find_option(self.parent_picker.options(), self.data.parent.to_str());
+        let parent_input = combo_box(
+            &self.parent_picker,
+            "Type to search labels and names...",
+            selected_parent,
+            |o| GuiMessage::DialogUpdate(DialogUpdate::Parent(o.label.into())),
+        );
+        let selected_child = find_option(self.child_picker.options(), self.data.child.to_str());
+        let child_input = combo_box(
+            &self.child_picker,
+            "Type to search labels and names...",
+            selected_child,
+            |o| GuiMessage::DialogUpdate(DialogUpdate::Child(o.label.into())),
+        );
+        let role_input = combo_box(
+            &self.role_picker,
+            "Type a role...",
+            Some(&self.data.role),
+            |r| GuiMessage::DialogUpdate(DialogUpdate::Role(r)),
+        )
+        .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())));
         let submit_button = Button::new(Text::new("Create")).on_press(GuiMessage::DialogSubmit);
         Column::new()
             .push(Text::new("Parent:"))
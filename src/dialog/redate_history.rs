@@ -4,9 +4,16 @@ use iced::{
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    history_view::{
+        dating::Dating,
+        properties::{change_history_item_properties, read_history_item, with_dating},
+    },
+};
 
-use super::{Dialog, DialogUpdate};
+use super::{new_history_item::dating_inputs, Dialog, DialogUpdate};
 
 #[derive(Debug, Clone)]
 pub(crate) struct RedateHistoryDialog {
@@ -26,6 +33,8 @@ pub(crate) struct RedateHistoryData {
     pub(self) old_day: Day,
     pub(self) new_year: Year,
     pub(self) new_day: Day,
+    pub(self) old_dating: Dating,
+    pub(self) new_dating: Dating,
 }
 
 impl RedateHistoryData {
@@ -36,11 +45,25 @@ impl RedateHistoryData {
             new_day: old_day,
             old_year,
             old_day,
+            old_dating: Dating::default(),
+            new_dating: Dating::default(),
         }
     }
 
+    pub(crate) fn set_dating(&mut self, dating: Dating) {
+        self.old_dating = dating.clone();
+        self.new_dating = dating;
+    }
+
     pub(crate) fn update_date_in_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        self.new_dating.validate(&self.new_year)?;
         db.redate_history_item(self.timestamp, self.new_year, self.new_day)?;
+        if self.new_dating != self.old_dating {
+            if let Some(item) = read_history_item(db, self.timestamp)? {
+                let properties = with_dating(&item.properties, &self.new_dating);
+                change_history_item_properties(db, self.timestamp, properties)?;
+            }
+        }
         Ok(())
     }
 
@@ -86,6 +109,7 @@ impl Dialog for RedateHistoryDialog {
             .push(year_input)
             .push(Text::new("Day (optional):"))
             .push(day_input)
+            .push(dating_inputs(&self.data.new_dating))
             .push(submit_button)
             .padding(5)
             .spacing(5)
@@ -96,6 +120,9 @@ impl Dialog for RedateHistoryDialog {
         match message {
             DialogUpdate::Year(Ok(year)) => self.data.new_year = year,
             DialogUpdate::Day(Ok(day)) => self.data.new_day = day,
+            DialogUpdate::Circa(circa) => self.data.new_dating.circa = circa,
+            DialogUpdate::RangeEnd(Ok(end)) => self.data.new_dating.range_end = end,
+            DialogUpdate::Precision(precision) => self.data.new_dating.precision = precision,
             _ => (),
         }
     }
@@ -0,0 +1,119 @@
+use iced::{
+    widget::{Button, Column, Text, TextInput},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    history_view::properties::{get_dating, read_history_item},
+};
+
+use super::{Dialog, DialogUpdate};
+
+/// The days of the items are only changed if a new day is given.
+#[derive(Debug, Clone)]
+pub(crate) struct RedateHistoryItemsDialog {
+    data: RedateHistoryItemsData,
+}
+
+impl RedateHistoryItemsDialog {
+    pub(crate) fn new(data: RedateHistoryItemsData) -> Self {
+        RedateHistoryItemsDialog { data }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RedateHistoryItemsData {
+    pub(self) timestamps: Vec<Timestamp>,
+    pub(self) new_year: Year,
+    pub(self) new_day: Option<Day>,
+}
+
+impl RedateHistoryItemsData {
+    /// The day is prefilled if all items share it, as they do when they are listed under a selected day.
+    pub(crate) fn new(timestamps: Vec<Timestamp>, year: Year, day: Option<Day>) -> Self {
+        RedateHistoryItemsData {
+            timestamps,
+            new_year: year,
+            new_day: day,
+        }
+    }
+
+    /// Nothing is written if the new year does not fit the dating of any of the items.
+    pub(crate) fn update_dates_in_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        let mut new_dates = vec![];
+        for timestamp in self.timestamps {
+            let item = match read_history_item(db, timestamp)? {
+                Some(item) => item,
+                None => continue,
+            };
+            get_dating(&item.properties).validate(&self.new_year)?;
+            new_dates.push((timestamp, self.new_day.unwrap_or(item.day)));
+        }
+        for (timestamp, day) in new_dates {
+            db.redate_history_item(timestamp, self.new_year, day)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn new_year(&self) -> Year {
+        self.new_year
+    }
+
+    pub(crate) fn new_day(&self) -> Option<Day> {
+        self.new_day
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_new_year(&mut self, year: Year) {
+        self.new_year = year;
+    }
+}
+
+impl Dialog for RedateHistoryItemsDialog {
+    fn header(&self) -> String {
+        format!("Redate {} history items", self.data.timestamps.len())
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let year_input = TextInput::new("", &self.data.new_year.to_string())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Year(i.try_into())));
+        let day_string = self
+            .data
+            .new_day
+            .map(|day| day.to_string())
+            .unwrap_or_default();
+        let day_input = TextInput::new("Keep each item's day", &day_string).on_input(|i| {
+            let day = if i.is_empty() {
+                Ok(None)
+            } else {
+                Day::try_from(i).map(Some)
+            };
+            GuiMessage::DialogUpdate(DialogUpdate::OptionalDay(day))
+        });
+        let submit_button = Button::new("Redate").on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new("Year:"))
+            .push(year_input)
+            .push(Text::new("Day (optional):"))
+            .push(day_input)
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Year(Ok(year)) => self.data.new_year = year,
+            DialogUpdate::OptionalDay(Ok(day)) => self.data.new_day = day,
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::RedateHistoryItems(self.data.to_owned())
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use iced::{
+    widget::{Button, Column, PickList, Text, TextInput},
+    Element,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+
+use super::{Dialog, DialogUpdate};
+
+/// How many labels are listed in the preview at most.
+const MAX_LISTED_LABELS: usize = 20;
+
+#[derive(Debug, Clone)]
+pub(crate) struct RenameDescriptorEverywhereDialog {
+    columns: Vec<EntityColumn>,
+    affected: Vec<Label>,
+    conflicting: Vec<Label>,
+    data: RenameDescriptorEverywhereData,
+}
+
+impl RenameDescriptorEverywhereDialog {
+    /// The columns of all entities are needed to preview which labels a rename affects.
+    pub(crate) fn new(data: RenameDescriptorEverywhereData, columns: Vec<EntityColumn>) -> Self {
+        let mut dialog = RenameDescriptorEverywhereDialog {
+            columns,
+            affected: vec![],
+            conflicting: vec![],
+            data,
+        };
+        dialog.affected = dialog.data.affected_labels(&dialog.columns);
+        dialog.conflicting = dialog.data.conflicting_labels(&dialog.columns);
+        dialog
+    }
+}
+
+/// What to do with a label that already has a descriptor of the new name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum RenameConflictHandling {
+    #[default]
+    Skip,
+    Overwrite,
+    Concatenate,
+}
+
+impl RenameConflictHandling {
+    const ALL: [RenameConflictHandling; 3] = [
+        RenameConflictHandling::Skip,
+        RenameConflictHandling::Overwrite,
+        RenameConflictHandling::Concatenate,
+    ];
+}
+
+impl Display for RenameConflictHandling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenameConflictHandling::Skip => write!(f, "Skip these entities"),
+            RenameConflictHandling::Overwrite => write!(f, "Overwrite existing descriptions"),
+            RenameConflictHandling::Concatenate => write!(f, "Concatenate descriptions"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RenameDescriptorEverywhereData {
+    pub(self) old_descriptor: Descriptor,
+    pub(self) new_descriptor: Descriptor,
+    pub(self) conflict_handling: RenameConflictHandling,
+}
+
+impl RenameDescriptorEverywhereData {
+    pub(crate) fn new(old_descriptor: Descriptor) -> Self {
+        RenameDescriptorEverywhereData {
+            new_descriptor: old_descriptor.clone(),
+            old_descriptor,
+            conflict_handling: RenameConflictHandling::default(),
+        }
+    }
+
+    fn labels_with(columns: &[EntityColumn], descriptor: &Descriptor) -> Vec<Label> {
+        let mut labels: Vec<Label> = columns
+            .iter()
+            .filter(|col| &col.descriptor == descriptor)
+            .map(|col| col.label.clone())
+            .collect();
+        labels.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+        labels.dedup();
+        labels
+    }
+
+    /// Labels that have the old descriptor, and therefore are affected by the rename.
+    pub(crate) fn affected_labels(&self, columns: &[EntityColumn]) -> Vec<Label> {
+        Self::labels_with(columns, &self.old_descriptor)
+    }
+
+    /// Affected labels that already have a descriptor of the new name.
+    pub(crate) fn conflicting_labels(&self, columns: &[EntityColumn]) -> Vec<Label> {
+        if self.new_descriptor == self.old_descriptor {
+            return vec![];
+        }
+        let targets: HashSet<&str> = columns
+            .iter()
+            .filter(|col| col.descriptor == self.new_descriptor)
+            .map(|col| col.label.to_str())
+            .collect();
+        let mut labels = self.affected_labels(columns);
+        labels.retain(|label| targets.contains(label.to_str()));
+        labels
+    }
+
+    pub(crate) fn update_descriptors_in_database(
+        self,
+        db: &LoreDatabase,
+    ) -> Result<(), LoreGuiError> {
+        if self.new_descriptor.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Cannot rename descriptor with empty new descriptor.".to_string(),
+            ));
+        }
+        if self.new_descriptor == self.old_descriptor {
+            return Err(LoreGuiError::InputError(
+                "The new descriptor is the same as the old one.".to_string(),
+            ));
+        }
+
+        let read_columns = |descriptor: &Descriptor| {
+            let search_params = EntityColumnSearchParams::new(
+                None,
+                Some(SqlSearchText::exact(descriptor.to_str())),
+            );
+            db.read_entity_columns(search_params)
+        };
+        let old_columns = read_columns(&self.old_descriptor)?;
+        let target_columns = read_columns(&self.new_descriptor)?;
+        let target_columns: HashMap<&str, &EntityColumn> = target_columns
+            .iter()
+            .map(|col| (col.label.to_str(), col))
+            .collect();
+        for old_col in old_columns {
+            let target_col = match target_columns.get(old_col.label.to_str()) {
+                Some(col) => col,
+                None => {
+                    db.change_entity_descriptor(
+                        (&old_col.label, old_col.descriptor),
+                        &self.new_descriptor,
+                    )?;
+                    continue;
+                }
+            };
+            let description: Description = match self.conflict_handling {
+                RenameConflictHandling::Skip => continue,
+                RenameConflictHandling::Overwrite => old_col.description.clone(),
+                RenameConflictHandling::Concatenate => format!(
+                    "{}\n\n{}",
+                    target_col.description.to_str().trim_end(),
+                    old_col.description.to_str()
+                )
+                .into(),
+            };
+            let label = old_col.label.clone();
+            db.change_entity_description((&label, &self.new_descriptor), &description)?;
+            // Should the old column remain, the target gets its own description back.
+            if let Err(e) = db.delete_entity_column((old_col.label, old_col.descriptor)) {
+                db.change_entity_description(
+                    (&label, &self.new_descriptor),
+                    &target_col.description,
+                )?;
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn new_descriptor(&self) -> &Descriptor {
+        &self.new_descriptor
+    }
+}
+
+fn label_list(labels: &[Label]) -> String {
+    let listed = labels
+        .iter()
+        .take(MAX_LISTED_LABELS)
+        .map(|label| label.to_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if labels.len() > MAX_LISTED_LABELS {
+        format!("{} and {} more", listed, labels.len() - MAX_LISTED_LABELS)
+    } else {
+        listed
+    }
+}
+
+impl Dialog for RenameDescriptorEverywhereDialog {
+    fn header(&self) -> String {
+        format!(
+            "Rename descriptor {} of all entities",
+            self.data.old_descriptor
+        )
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let new_descriptor_input = TextInput::new("", self.data.new_descriptor.to_str())
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Descriptor(i.into())));
+        let (affected, conflicting) = (&self.affected, &self.conflicting);
+        let mut col = Column::new()
+            .push(Text::new("New Descriptor"))
+            .push(new_descriptor_input)
+            .push(Text::new(format!(
+                "Affected entities ({}): {}",
+                affected.len(),
+                label_list(affected)
+            )));
+        if !conflicting.is_empty() {
+            let handling_input = PickList::new(
+                RenameConflictHandling::ALL,
+                Some(self.data.conflict_handling),
+                |h| GuiMessage::DialogUpdate(DialogUpdate::ConflictHandling(h)),
+            );
+            col = col
+                .push(Text::new(format!(
+                    "Already have {}: {}",
+                    self.data.new_descriptor,
+                    label_list(conflicting)
+                )))
+                .push(handling_input);
+        }
+        let submit_button = Button::new(Text::new("Rename")).on_press(GuiMessage::DialogSubmit);
+        col.push(submit_button).padding(5).spacing(5).into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Descriptor(new_descriptor) => {
+                self.data.new_descriptor = new_descriptor;
+                self.conflicting = self.data.conflicting_labels(&self.columns);
+            }
+            DialogUpdate::ConflictHandling(handling) => self.data.conflict_handling = handling,
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::RenameDescriptorEverywhere(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    #[test]
+    fn conflicting_labels_are_skipped_or_concatenated() {
+        let db = example_database();
+        let labels = example_labels();
+        let descriptors: Vec<Descriptor> =
+            example_descriptors().into_iter().map(|(d, _)| d).collect();
+        let columns = db
+            .read_entity_columns(EntityColumnSearchParams::new(None, None))
+            .unwrap();
+        let description_of = |label: &Label, descriptor: &Descriptor| {
+            let search_params = EntityColumnSearchParams::new(
+                Some(SqlSearchText::exact(label.to_str())),
+                Some(SqlSearchText::exact(descriptor.to_str())),
+            );
+            db.read_entity_columns(search_params)
+                .unwrap()
+                .pop()
+                .map(|col| col.description)
+        };
+
+        let mut data = RenameDescriptorEverywhereData::new(descriptors[0].clone());
+        data.new_descriptor = descriptors[1].clone();
+        assert_eq!(data.affected_labels(&columns).len(), labels.len());
+        assert_eq!(data.conflicting_labels(&columns).len(), labels.len());
+        data.clone().update_descriptors_in_database(&db).unwrap();
+        assert!(description_of(&labels[0], &descriptors[0]).is_some());
+
+        let old_description = description_of(&labels[0], &descriptors[0]).unwrap();
+        let target_description = description_of(&labels[0], &descriptors[1]).unwrap();
+        data.conflict_handling = RenameConflictHandling::Concatenate;
+        data.update_descriptors_in_database(&db).unwrap();
+        for label in labels.iter() {
+            assert_eq!(description_of(label, &descriptors[0]), None);
+        }
+        let merged = description_of(&labels[0], &descriptors[1]).unwrap();
+        assert!(merged
+            .to_str()
+            .starts_with(target_description.to_str().trim_end()));
+        assert!(merged.to_str().ends_with(old_description.to_str()));
+    }
+
+    #[test]
+    fn descriptor_is_renamed_for_all_labels_without_conflict() {
+        let db = example_database();
+        let labels = example_labels();
+        let descriptors: Vec<Descriptor> =
+            example_descriptors().into_iter().map(|(d, _)| d).collect();
+        let mut data = RenameDescriptorEverywhereData::new(descriptors[2].clone());
+        data.new_descriptor = "Appearance".into();
+        data.update_descriptors_in_database(&db).unwrap();
+
+        let search_params =
+            EntityColumnSearchParams::new(None, Some(SqlSearchText::exact("Appearance")));
+        assert_eq!(
+            db.read_entity_columns(search_params).unwrap().len(),
+            labels.len()
+        );
+        let search_params = EntityColumnSearchParams::new(
+            None,
+            Some(SqlSearchText::exact(descriptors[2].to_str())),
+        );
+        assert!(db.read_entity_columns(search_params).unwrap().is_empty());
+    }
+}
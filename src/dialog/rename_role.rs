@@ -0,0 +1,136 @@
+use iced::{
+    widget::{combo_box, Button, Column, Text},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    relationship_view::{
+        inverse_roles::{inverse_role_pair_key, read_inverse_role_pairs, relationship_exists},
+        roles::read_relationships_with_role,
+    },
+};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct RenameRoleDialog {
+    role_picker: combo_box::State<Role>,
+    data: RenameRoleData,
+}
+
+impl RenameRoleDialog {
+    pub(crate) fn new(data: RenameRoleData, roles: Vec<Role>) -> Self {
+        RenameRoleDialog {
+            role_picker: combo_box::State::new(roles),
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RenameRoleData {
+    pub(self) old_role: Role,
+    pub(self) new_role: Role,
+}
+
+impl RenameRoleData {
+    pub(crate) fn new(old_role: Role) -> Self {
+        RenameRoleData {
+            new_role: old_role.clone(),
+            old_role,
+        }
+    }
+
+    /// Renaming to a role in use merges the two, keeping duplicated relationships once.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.new_role.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(
+                "Cannot rename role to an empty string.".to_string(),
+            ));
+        }
+        if self.new_role == self.old_role {
+            return Ok(());
+        }
+
+        for rel in read_relationships_with_role(db, &self.old_role)? {
+            let renamed = EntityRelationship {
+                parent: rel.parent.clone(),
+                child: rel.child.clone(),
+                role: self.new_role.clone(),
+            };
+            if relationship_exists(db, &renamed)? {
+                db.delete_relationship(rel)?;
+            } else {
+                db.change_relationship_role(rel, &self.new_role)?;
+            }
+        }
+
+        let pairs = read_inverse_role_pairs(db)?;
+        if let Some(pair) = pairs.iter().find(|pair| pair.involves(&self.old_role)) {
+            db.delete_entity_column(inverse_role_pair_key(&pair.role))?;
+            if !pairs.iter().any(|pair| pair.involves(&self.new_role)) {
+                let mut renamed = pair.clone();
+                for role in [&mut renamed.role, &mut renamed.inverse] {
+                    if *role == self.old_role {
+                        *role = self.new_role.clone();
+                    }
+                }
+                db.write_entity_columns(vec![renamed.to_column()])?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn old_role(&self) -> &Role {
+        &self.old_role
+    }
+
+    pub(crate) fn new_role(&self) -> &Role {
+        &self.new_role
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_new_role(&mut self, role: Role) {
+        self.new_role = role;
+    }
+}
+
+impl Dialog for RenameRoleDialog {
+    fn header(&self) -> String {
+        format!("Rename or merge role {}", self.data.old_role)
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let role_input = combo_box(
+            &self.role_picker,
+            "Type a role...",
+            Some(&self.data.new_role),
+            |r| GuiMessage::DialogUpdate(DialogUpdate::Role(r)),
+        )
+        .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())));
+        let submit_button = Button::new(Text::new("Rename")).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new("New Role:"))
+            .push(role_input)
+            .push(Text::new(
+                "Choosing a role that is already in use merges both roles.",
+            ))
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::Role(role) = message {
+            self.data.new_role = role;
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::RenameRole(self.data.to_owned())
+    }
+}
@@ -0,0 +1,155 @@
+use iced::{
+    widget::{combo_box, Button, Column, Text},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    relationship_view::{integrity::read_existing_labels, inverse_roles::relationship_exists},
+};
+
+use super::{
+    label_picker::{find_option, LabelOption},
+    Dialog, DialogUpdate,
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct RetargetRelationshipDialog {
+    parent_picker: combo_box::State<LabelOption>,
+    child_picker: combo_box::State<LabelOption>,
+    data: RetargetRelationshipData,
+}
+
+impl RetargetRelationshipDialog {
+    pub(crate) fn new(data: RetargetRelationshipData, labels: Vec<LabelOption>) -> Self {
+        RetargetRelationshipDialog {
+            parent_picker: combo_box::State::new(labels.clone()),
+            child_picker: combo_box::State::new(labels),
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RetargetRelationshipData {
+    pub(self) old_relationship: EntityRelationship,
+    pub(self) new_parent: Parent,
+    pub(self) new_child: Child,
+}
+
+impl RetargetRelationshipData {
+    pub(crate) fn new(old_relationship: EntityRelationship) -> Self {
+        RetargetRelationshipData {
+            new_parent: old_relationship.parent.clone(),
+            new_child: old_relationship.child.clone(),
+            old_relationship,
+        }
+    }
+
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        let labels = read_existing_labels(db)?;
+        for end in [self.new_parent.to_str(), self.new_child.to_str()] {
+            if !labels.iter().any(|label| label.to_str() == end) {
+                return Err(LoreGuiError::InputError(format!(
+                    "There is no entity with label {}.",
+                    end
+                )));
+            }
+        }
+
+        let new_relationship = EntityRelationship {
+            parent: self.new_parent,
+            child: self.new_child,
+            role: self.old_relationship.role.clone(),
+        };
+        if new_relationship == self.old_relationship {
+            return Ok(());
+        }
+        if relationship_exists(db, &new_relationship)? {
+            return Err(LoreGuiError::InputError(format!(
+                "There already is a {} relationship between {} and {}.",
+                new_relationship.role, new_relationship.parent, new_relationship.child
+            )));
+        }
+        // Writing first keeps the original relationship should the write fail.
+        db.write_relationships(vec![new_relationship])?;
+        db.delete_relationship(self.old_relationship)?;
+        Ok(())
+    }
+
+    pub(crate) fn new_parent(&self) -> &Parent {
+        &self.new_parent
+    }
+
+    pub(crate) fn new_child(&self) -> &Child {
+        &self.new_child
+    }
+
+    pub(crate) fn role(&self) -> &Role {
+        &self.old_relationship.role
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_new_child(&mut self, child: Child) {
+        self.new_child = child;
+    }
+}
+
+impl Dialog for RetargetRelationshipDialog {
+    fn header(&self) -> String {
+        format!(
+            "Retarget {} relationship between {} and {}",
+            self.data.old_relationship.role,
+            self.data.old_relationship.parent,
+            self.data.old_relationship.child
+        )
+    }
+
+    fn body(&self) -> Element<'_, GuiMessage> {
+        let selected_parent =
+            find_option(self.parent_picker.options(), self.data.new_parent.to_str());
+        let parent_input = combo_box(
+            &self.parent_picker,
+            "Type to search labels and names...",
+            selected_parent,
+            |o| GuiMessage::DialogUpdate(DialogUpdate::Parent(o.label.into())),
+        );
+        let selected_child = find_option(self.child_picker.options(), self.data.new_child.to_str());
+        let child_input = combo_box(
+            &self.child_picker,
+            "Type to search labels and names...",
+            selected_child,
+            |o| GuiMessage::DialogUpdate(DialogUpdate::Child(o.label.into())),
+        );
+        let submit_button = Button::new(Text::new("Retarget")).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(Text::new(format!(
+                "Parent (currently {}):",
+                self.data.new_parent
+            )))
+            .push(parent_input)
+            .push(Text::new(format!(
+                "Child (currently {}):",
+                self.data.new_child
+            )))
+            .push(child_input)
+            .push(submit_button)
+            .padding(5)
+            .spacing(5)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Parent(parent) => self.data.new_parent = parent,
+            DialogUpdate::Child(child) => self.data.new_child = child,
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::RetargetRelationship(self.data.to_owned())
+    }
+}
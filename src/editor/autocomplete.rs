@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use iced::widget::text_editor::{Action, Edit};
+use lorecore::types::*;
+
+use crate::dialog::label_picker::LabelOption;
+
+use super::EditorState;
+
+const MAX_SUGGESTIONS: usize = 8;
+
+/// A label reference as it is written in descriptions and history item contents.
+pub(crate) fn reference(label: &Label) -> String {
+    format!("\\reference{{{}}}", label)
+}
+
+/// A label being typed after `@` or `[[`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Completion {
+    trigger_length: usize,
+    pub(super) query: String,
+}
+
+fn is_label_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+impl EditorState {
+    pub(crate) fn set_label_options(&mut self, label_options: Vec<LabelOption>) {
+        self.label_options = label_options;
+    }
+
+    pub(crate) fn get_label_options(&self) -> &[LabelOption] {
+        &self.label_options
+    }
+
+    /// Starts, continues or cancels a completion depending on what the action does to the text.
+    pub(super) fn track_completion(&mut self, action: &Action) {
+        let previous = self.last_inserted.take();
+        match action {
+            Action::Edit(Edit::Insert(c)) => {
+                self.last_inserted = Some(*c);
+                let trigger_length = match *c {
+                    '@' => 1,
+                    '[' if previous == Some('[') => 2,
+                    _ => 0,
+                };
+                if let (Some(completion), true) = (&mut self.completion, is_label_char(*c)) {
+                    completion.query.push(*c);
+                } else if trigger_length > 0 {
+                    self.completion = Some(Completion {
+                        trigger_length,
+                        query: String::new(),
+                    });
+                } else {
+                    self.completion = None;
+                }
+            }
+            Action::Edit(Edit::Backspace) => {
+                if let Some(completion) = &mut self.completion {
+                    if completion.query.pop().is_none() {
+                        self.completion = None;
+                    }
+                }
+            }
+            Action::Scroll { .. } => self.last_inserted = previous,
+            _ => self.completion = None,
+        }
+    }
+
+    /// The labels whose label or name contain the typed text, those starting with it first.
+    pub(crate) fn suggestions(&self) -> Vec<&LabelOption> {
+        let query = match &self.completion {
+            Some(completion) => completion.query.to_lowercase(),
+            None => return vec![],
+        };
+        let texts = |option: &LabelOption| {
+            let label = option.label.to_str().to_lowercase();
+            let name = option.name.as_deref().unwrap_or_default().to_lowercase();
+            (label, name)
+        };
+        let mut suggestions: Vec<(bool, &LabelOption)> = self
+            .label_options
+            .iter()
+            .filter(|option| !option.label.is_protected())
+            .filter_map(|option| {
+                let (label, name) = texts(option);
+                if label.starts_with(&query) || name.starts_with(&query) {
+                    Some((true, option))
+                } else if label.contains(&query) || name.contains(&query) {
+                    Some((false, option))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.0.cmp(&a.0));
+        suggestions
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, option)| option)
+            .collect()
+    }
+
+    /// Replaces the trigger and the typed text with a reference to the label.
+    pub(crate) fn complete(&mut self, label: &Label) {
+        let completion = match self.completion.take() {
+            Some(completion) => completion,
+            None => return,
+        };
+        let typed = completion.trigger_length + completion.query.chars().count();
+        for _ in 0..typed {
+            self.current_content.perform(Action::Edit(Edit::Backspace));
+        }
+        let reference = Arc::new(reference(label));
+        self.current_content
+            .perform(Action::Edit(Edit::Paste(reference)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(state: &mut EditorState, text: &str) {
+        for c in text.chars() {
+            state.perform(Action::Edit(Edit::Insert(c)));
+        }
+    }
+
+    fn option(label: &str, name: &str) -> LabelOption {
+        LabelOption {
+            label: label.into(),
+            name: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn typing_after_trigger_suggests_and_completes_labels() {
+        let mut state = EditorState::new("");
+        state.set_label_options(vec![
+            option("frodo", "Frodo Baggins"),
+            option("bilbo", "Bilbo Baggins"),
+            option("sam", "Samwise Gamgee"),
+        ]);
+        type_text(&mut state, "Ask [[bag");
+        let suggested: Vec<&str> = state
+            .suggestions()
+            .iter()
+            .map(|option| option.label.to_str())
+            .collect();
+        assert_eq!(suggested, vec!["frodo", "bilbo"]);
+
+        state.complete(&"bilbo".into());
+        assert_eq!(state.get_text().trim_end(), "Ask \\reference{bilbo}");
+        assert!(state.suggestions().is_empty());
+
+        type_text(&mut state, " and @sam");
+        assert_eq!(state.suggestions().len(), 1);
+        type_text(&mut state, " ");
+        assert!(state.suggestions().is_empty());
+    }
+}
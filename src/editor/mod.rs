@@ -1,11 +1,21 @@
 use iced::widget::text_editor;
 
+use crate::dialog::label_picker::LabelOption;
+
+use self::autocomplete::Completion;
+
+pub(crate) mod autocomplete;
 pub(crate) mod widget;
 
 #[derive(Debug)]
 pub(super) struct EditorState {
     current_content: text_editor::Content,
     persisted_text: String,
+    /// What the persisted text belongs to, such as the selected label and descriptor.
+    source: String,
+    completion: Option<Completion>,
+    last_inserted: Option<char>,
+    label_options: Vec<LabelOption>,
 }
 
 impl Default for EditorState {
@@ -15,6 +25,10 @@ impl Default for EditorState {
         Self {
             current_content,
             persisted_text,
+            source: String::new(),
+            completion: None,
+            last_inserted: None,
+            label_options: vec![],
         }
     }
 }
@@ -26,10 +40,27 @@ impl EditorState {
         Self {
             current_content,
             persisted_text,
+            source: String::new(),
+            completion: None,
+            last_inserted: None,
+            label_options: vec![],
+        }
+    }
+
+    /// Keeps unsaved edits unless the source or its persisted text changed.
+    pub(super) fn update_persisted(&mut self, source: String, text: &str) {
+        if source != self.source
+            || text_editor::Content::with_text(text).text() != self.persisted_text
+        {
+            let label_options = std::mem::take(&mut self.label_options);
+            *self = Self::new(text);
+            self.source = source;
+            self.label_options = label_options;
         }
     }
 
     pub(super) fn perform(&mut self, action: text_editor::Action) {
+        self.track_completion(&action);
         self.current_content.perform(action);
     }
 
@@ -39,6 +70,7 @@ impl EditorState {
 
     pub(super) fn reset(&mut self) {
         self.current_content = text_editor::Content::with_text(&self.persisted_text);
+        self.completion = None;
     }
 
     pub(super) fn is_changed(&self) -> bool {
@@ -63,4 +95,15 @@ mod tests {
         editor_state.reset();
         assert_eq!(editor_state.is_changed(), false);
     }
+
+    #[test]
+    fn edits_are_dropped_when_another_source_has_the_same_text() {
+        let mut editor_state = EditorState::default();
+        editor_state.update_persisted("first".to_string(), "Same text");
+        editor_state.perform(text_editor::Action::Edit(text_editor::Edit::Insert('i')));
+        editor_state.update_persisted("first".to_string(), "Same text");
+        assert!(editor_state.is_changed());
+        editor_state.update_persisted("second".to_string(), "Same text");
+        assert!(!editor_state.is_changed());
+    }
 }
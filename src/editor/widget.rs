@@ -1,21 +1,24 @@
 use iced::{
-    widget::{button, text_editor, Column},
+    widget::{button, text_editor, Column, Row},
     Length,
 };
+use lorecore::types::*;
 
 use crate::{app::message_handling::GuiMessage, style::header};
 
 use super::EditorState;
 
-pub(crate) fn view<'a, M>(
+pub(crate) fn view<'a, M, C>(
     title: &'static str,
     state: &'a EditorState,
     on_action: M,
+    on_complete: C,
     on_discard: GuiMessage,
     on_save: GuiMessage,
 ) -> Column<'a, GuiMessage>
 where
     M: 'static + Clone + Fn(text_editor::Action) -> GuiMessage,
+    C: Fn(Label) -> GuiMessage,
 {
     let editor = text_editor(&state.current_content).on_action(on_action);
     let mut discard_button = button("Discard Changes");
@@ -25,9 +28,16 @@ where
         save_button = save_button.on_press(on_save);
     }
 
+    let mut suggestions = Row::new().spacing(5);
+    for option in state.suggestions() {
+        suggestions = suggestions
+            .push(button(option.to_string()).on_press(on_complete(option.label.clone())));
+    }
+
     Column::new()
         .push(header(title))
         .push(editor)
+        .push(suggestions)
         .push(discard_button)
         .push(save_button)
         .padding(5)
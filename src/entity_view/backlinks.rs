@@ -0,0 +1,115 @@
+use lorecore::types::*;
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    dialog::find_replace::{TextLocation, TextSource},
+    editor::autocomplete::reference,
+};
+
+/// How many characters around a mention are shown in its snippet.
+const SNIPPET_LENGTH: usize = 30;
+
+/// A description or history item content that mentions an entity.
+#[derive(Debug, Clone)]
+pub(crate) struct Backlink {
+    pub(crate) location: TextLocation,
+    pub(crate) snippet: String,
+}
+
+impl Backlink {
+    pub(crate) fn on_press(&self) -> GuiMessage {
+        match &self.location {
+            TextLocation::Description(label, descriptor) => {
+                GuiMessage::GoToDescription(label.clone(), descriptor.clone())
+            }
+            TextLocation::HistoryContent(year, day, timestamp) => {
+                GuiMessage::GoToHistoryItem(*year, *day, *timestamp)
+            }
+        }
+    }
+}
+
+/// All texts outside the entity itself that reference its label or contain its name as a word.
+pub(crate) fn find_backlinks(
+    sources: &[TextSource],
+    label: &Label,
+    name: Option<&str>,
+) -> Vec<Backlink> {
+    let name_regex = name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .and_then(|name| name_regex(name).ok());
+    let label_reference = reference(label);
+    sources
+        .iter()
+        .filter(|source| match &source.location {
+            TextLocation::Description(other, _) => other != label,
+            TextLocation::HistoryContent(_, _, _) => true,
+        })
+        .filter_map(|source| {
+            let mention = match source.text.find(&label_reference) {
+                Some(start) => Some((start, start + label_reference.len())),
+                None => name_regex
+                    .as_ref()
+                    .and_then(|regex| regex.find(&source.text))
+                    .map(|found| (found.start(), found.end())),
+            }?;
+            Some(Backlink {
+                location: source.location.clone(),
+                snippet: snippet(&source.text, mention),
+            })
+        })
+        .collect()
+}
+
+/// Matches the name as a whole word, ignoring case.
+pub(crate) fn name_regex(name: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(&format!(r"\b{}\b", regex::escape(name)))
+        .case_insensitive(true)
+        .build()
+}
+
+/// The mentioned text together with a few characters before and after.
+pub(crate) fn snippet(text: &str, (start, end): (usize, usize)) -> String {
+    let before = &text[..start];
+    let before_start = before
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_LENGTH)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let after: String = text[end..].chars().take(SNIPPET_LENGTH).collect();
+    format!("...{}{}...", &text[before_start..end], after).replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn description(label: &str, text: &str) -> TextSource {
+        TextSource {
+            location: TextLocation::Description(label.into(), "descriptor".into()),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn references_and_names_of_other_texts_are_found() {
+        let label: Label = "frodo".into();
+        let sources = vec![
+            description("frodo", "Frodo Baggins himself"),
+            description("sam", "Follows \\reference{frodo} everywhere"),
+            description("bilbo", "Adopted frodo baggins"),
+            description("merry", "Cousin of Frodobert"),
+        ];
+        let backlinks = find_backlinks(&sources, &label, Some("Frodo Baggins\n"));
+
+        let mentioning: Vec<String> = backlinks
+            .iter()
+            .map(|backlink| backlink.location.to_string())
+            .collect();
+        assert_eq!(mentioning, vec!["sam: descriptor", "bilbo: descriptor"]);
+        assert_eq!(backlinks[1].snippet, "...Adopted frodo baggins...");
+    }
+}
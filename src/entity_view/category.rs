@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+pub(crate) const CATEGORY_DESCRIPTOR: &str = "_category";
+
+/// The labels of all entities together with their category.
+pub(crate) fn read_label_categories(
+    db: &LoreDatabase,
+) -> Result<Vec<(Label, String)>, LoreGuiError> {
+    let search_params =
+        EntityColumnSearchParams::new(None, Some(SqlSearchText::exact(CATEGORY_DESCRIPTOR)));
+    let categories = db
+        .read_entity_columns(search_params)?
+        .into_iter()
+        .map(|col| (col.label, col.description.to_str().to_string()))
+        .collect();
+    Ok(categories)
+}
+
+/// Restricts a list of labels to entities of a category, unless no category is chosen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CategoryFilter(pub(crate) Option<String>);
+
+impl CategoryFilter {
+    pub(crate) fn options(label_categories: &[(Label, String)]) -> Vec<CategoryFilter> {
+        let mut categories: Vec<String> = label_categories
+            .iter()
+            .map(|(_, category)| category.clone())
+            .collect();
+        categories.sort();
+        categories.dedup();
+        std::iter::once(CategoryFilter(None))
+            .chain(categories.into_iter().map(|c| CategoryFilter(Some(c))))
+            .collect()
+    }
+
+    pub(crate) fn matches(&self, label: &Label, label_categories: &[(Label, String)]) -> bool {
+        match &self.0 {
+            None => true,
+            Some(category) => label_categories
+                .iter()
+                .any(|(l, c)| l == label && c == category),
+        }
+    }
+}
+
+impl Display for CategoryFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(category) => write!(f, "{}", category),
+            None => write!(f, "[all categories]"),
+        }
+    }
+}
@@ -3,20 +3,31 @@ use lorecore::{
     extractions::{extract_descriptors, extract_labels},
     sql::{
         lore_database::LoreDatabase,
-        search_params::{EntityColumnSearchParams, SqlSearchText},
+        search_params::{
+            EntityColumnSearchParams, HistoryItemSearchParams, RelationshipSearchParams,
+            SqlSearchText,
+        },
     },
     types::*,
 };
 
 use crate::{
     db_col_view::{entry::DbColViewEntry, ColViewMes},
-    dialog::{relabel_entity::RelabelEntityData, rename_descriptor::RenameDescriptorData},
+    dialog::{
+        duplicate_entity::DuplicateEntityData, find_replace::read_text_sources,
+        relabel_entity::RelabelEntityData, rename_descriptor::RenameDescriptorData,
+    },
     editor::EditorState,
     errors::LoreGuiError,
+    history_view::properties::get_participants,
 };
 
+use self::backlinks::{find_backlinks, Backlink};
+
 use super::db_col_view::state::DbColViewState;
 
+pub(crate) mod backlinks;
+pub(crate) mod category;
 pub(crate) mod widget;
 
 #[derive(Debug, Default)]
@@ -26,21 +37,42 @@ pub(super) struct EntityViewState {
     pub(super) label_view_state: DbColViewState<Label>,
     pub(super) descriptor_view_state: DbColViewState<Descriptor>,
     pub(super) current_description: EditorState,
+    pub(super) history_items: Vec<HistoryItem>,
+    pub(super) backlinks: Vec<Backlink>,
+    /// The label that the history items and backlinks were found for.
+    pub(super) texts_label: Option<Label>,
+    /// Set after texts may have changed, so that they are searched anew.
+    pub(super) texts_outdated: bool,
+    pub(super) outgoing_relationships: Vec<EntityRelationship>,
+    pub(super) incoming_relationships: Vec<EntityRelationship>,
+    pub(super) show_overview: bool,
+    pub(super) overview: Vec<(Descriptor, Description)>,
 }
 
 #[derive(Debug, Clone)]
 pub(super) enum EntityViewMessage {
     NewEntity,
     RelabelEntity(RelabelEntityData),
+    DuplicateEntity(DuplicateEntityData),
+    MergeEntity(Label),
     DeleteEntity(Label),
+    DeleteEntities(Vec<Label>),
+    ChangeCategory(Vec<Label>),
+    ExportEntities(Vec<Label>),
     NewDescriptor(Label),
     RenameDescriptor(RenameDescriptorData),
+    RenameDescriptorEverywhere(Descriptor),
     DeleteDescriptor(Label, Descriptor),
+    DeleteDescriptors(Vec<Label>, Vec<Descriptor>),
+    NewRelationship(Label),
     LabelViewUpdate(ColViewMes<Label>),
     DescriptorViewUpdate(ColViewMes<Descriptor>),
     DescriptionUpdate(text_editor::Action),
+    DescriptionComplete(Label),
     DescriptionDiscard,
     DescriptionSave,
+    ShowOverview(bool),
+    EditDescriptor(Descriptor),
 }
 
 impl EntityViewState {
@@ -124,6 +156,114 @@ impl EntityViewState {
         Ok(description)
     }
 
+    /// Every descriptor of the selected label together with its description.
+    pub(super) fn get_current_overview(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<(Descriptor, Description)>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let label = match self.get_selected_label() {
+            Some(label) => label,
+            None => return Ok(vec![]),
+        };
+
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+        let mut entity_columns = db.read_entity_columns(search_params)?;
+        if !self.display_protected {
+            entity_columns.retain(|col| !col.descriptor.is_protected());
+        }
+        entity_columns.sort_by(|a, b| a.descriptor.to_str().cmp(b.descriptor.to_str()));
+        let overview = entity_columns
+            .into_iter()
+            .map(|col| (col.descriptor, col.description))
+            .collect();
+        Ok(overview)
+    }
+
+    pub(super) fn get_current_history_items(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<HistoryItem>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let label = match self.get_selected_label() {
+            Some(label) => label,
+            None => return Ok(vec![]),
+        };
+
+        let search_params = HistoryItemSearchParams::new(None, None, None, None);
+        let mut history_items = db.read_history_items(search_params)?;
+        history_items.retain(|item| get_participants(&item.properties).contains(&label));
+        history_items.sort_by_key(|item| {
+            (
+                item.year.to_int(),
+                item.day.to_int(),
+                item.timestamp.to_int(),
+            )
+        });
+        Ok(history_items)
+    }
+
+    /// The descriptions of other entities and the history items that mention the selected label.
+    pub(super) fn get_current_backlinks(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<Backlink>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let label = match self.get_selected_label() {
+            Some(label) => label,
+            None => return Ok(vec![]),
+        };
+
+        let search_params = EntityColumnSearchParams::new(
+            Some(SqlSearchText::exact(label.to_str())),
+            Some(SqlSearchText::exact("_name")),
+        );
+        let name = db
+            .read_entity_columns(search_params)?
+            .into_iter()
+            .next()
+            .map(|col| col.description.to_str().to_string());
+        let sources = read_text_sources(db)?;
+        Ok(find_backlinks(&sources, &label, name.as_deref()))
+    }
+
+    /// The relationships in which the selected label is the parent and the child, respectively.
+    pub(super) fn get_current_relationships(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<(Vec<EntityRelationship>, Vec<EntityRelationship>), LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok((vec![], vec![])),
+        };
+        let label = match self.get_selected_label() {
+            Some(label) => label,
+            None => return Ok((vec![], vec![])),
+        };
+
+        let as_parent =
+            RelationshipSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+        let as_child =
+            RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label.to_str())));
+        let mut outgoing = db.read_relationships(as_parent)?;
+        let mut incoming = db.read_relationships(as_child)?;
+        if !self.display_protected {
+            outgoing.retain(|rel| !rel.role.is_protected());
+            incoming.retain(|rel| !rel.role.is_protected());
+        }
+        Ok((outgoing, incoming))
+    }
+
     pub(super) fn get_selected_label(&self) -> Option<Label> {
         self.label_view_state.get_selected().0.clone()
     }
@@ -1,19 +1,24 @@
-use iced::widget::button;
+use iced::widget::{button, Button, Text, Toggler};
 use iced::{
-    widget::{Column, Row},
+    widget::{Column, Row, Scrollable},
     Alignment, Element, Length,
 };
+use lorecore::types::*;
 
 use crate::app::message_handling::GuiMessage;
 use crate::content::content_view;
+use crate::dialog::duplicate_entity::DuplicateEntityData;
 use crate::dialog::relabel_entity::RelabelEntityData;
 use crate::dialog::rename_descriptor::RenameDescriptorData;
+use crate::history_view::properties::get_dating;
+use crate::relationship_view::RelationshipViewMessage;
+use crate::style::header;
 use crate::{db_col_view, editor};
 
 use super::{EntityViewMessage, EntityViewState};
 
 pub(crate) fn new(state: &EntityViewState) -> Element<'_, GuiMessage> {
-    let mut col = Column::new();
+    let mut col = Column::new().push(overview_toggler(state));
     if state.edit_mode {
         col = col
             .push(label_buttons(state))
@@ -22,16 +27,48 @@ pub(crate) fn new(state: &EntityViewState) -> Element<'_, GuiMessage> {
     col.push(col_views(state)).into()
 }
 
+fn overview_toggler(state: &EntityViewState) -> Row<'_, GuiMessage> {
+    let toggler = Toggler::new(state.show_overview)
+        .label("Show all descriptors")
+        .on_toggle(|show| GuiMessage::EntityViewUpd(EntityViewMessage::ShowOverview(show)));
+    let labels = state.label_view_state.get_multi_selected();
+    let mut export = button("Export Selected Entities");
+    if !labels.is_empty() {
+        export = export.on_press(GuiMessage::EntityViewUpd(
+            EntityViewMessage::ExportEntities(labels),
+        ));
+    }
+    Row::new()
+        .push(toggler)
+        .push(export)
+        .align_y(Alignment::Center)
+        .spacing(10)
+        .padding(5)
+}
+
 fn label_buttons(state: &EntityViewState) -> Row<'_, GuiMessage> {
     let new_entity =
         button("New Entity").on_press(GuiMessage::EntityViewUpd(EntityViewMessage::NewEntity));
+    let labels = state.label_view_state.get_multi_selected();
+    if labels.len() > 1 {
+        return bulk_label_buttons(new_entity, labels);
+    }
     let mut relabel_entity = button("Relabel Entity");
+    let mut duplicate_entity = button("Duplicate Entity");
+    let mut merge_entity = button("Merge Entity");
     let mut delete_entity = button("Delete Entity");
     if let Some(label) = state.get_selected_label() {
         let relabel_entity_data = RelabelEntityData::new(label.clone());
         relabel_entity = relabel_entity.on_press(GuiMessage::EntityViewUpd(
             EntityViewMessage::RelabelEntity(relabel_entity_data),
         ));
+        let duplicate_entity_data = DuplicateEntityData::new(label.clone());
+        duplicate_entity = duplicate_entity.on_press(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DuplicateEntity(duplicate_entity_data),
+        ));
+        merge_entity = merge_entity.on_press(GuiMessage::EntityViewUpd(
+            EntityViewMessage::MergeEntity(label.clone()),
+        ));
         delete_entity = delete_entity.on_press(GuiMessage::EntityViewUpd(
             EntityViewMessage::DeleteEntity(label.clone()),
         ));
@@ -39,15 +76,38 @@ fn label_buttons(state: &EntityViewState) -> Row<'_, GuiMessage> {
     Row::new()
         .push(new_entity)
         .push(relabel_entity)
+        .push(duplicate_entity)
+        .push(merge_entity)
         .push(delete_entity)
         .spacing(5)
         .padding(5)
 }
 
+fn bulk_label_buttons(
+    new_entity: Button<'_, GuiMessage>,
+    labels: Vec<Label>,
+) -> Row<'_, GuiMessage> {
+    let count = labels.len();
+    let change_category = button("Change Category").on_press(GuiMessage::EntityViewUpd(
+        EntityViewMessage::ChangeCategory(labels.clone()),
+    ));
+    let delete_entities = button(Text::new(format!("Delete {} Entities", count))).on_press(
+        GuiMessage::EntityViewUpd(EntityViewMessage::DeleteEntities(labels)),
+    );
+    Row::new()
+        .push(new_entity)
+        .push(change_category)
+        .push(delete_entities)
+        .spacing(5)
+        .padding(5)
+}
+
 fn descriptor_buttons(state: &EntityViewState) -> Row<'_, GuiMessage> {
     let mut new_descriptor = button("New Descriptor");
     let mut rename_descriptor = button("Rename Descriptor");
+    let mut rename_everywhere = button("Rename Everywhere");
     let mut delete_descriptor = button("Delete Descriptor");
+    let mut link_mentions = button("Link Mentions");
     if let Some(label) = state.get_selected_label() {
         new_descriptor = new_descriptor.on_press(GuiMessage::EntityViewUpd(
             EntityViewMessage::NewDescriptor(label.clone()),
@@ -58,43 +118,186 @@ fn descriptor_buttons(state: &EntityViewState) -> Row<'_, GuiMessage> {
             rename_descriptor = rename_descriptor.on_press(GuiMessage::EntityViewUpd(
                 EntityViewMessage::RenameDescriptor(rename_descriptor_data),
             ));
+            rename_everywhere = rename_everywhere.on_press(GuiMessage::EntityViewUpd(
+                EntityViewMessage::RenameDescriptorEverywhere(descriptor.clone()),
+            ));
             delete_descriptor = delete_descriptor.on_press(GuiMessage::EntityViewUpd(
                 EntityViewMessage::DeleteDescriptor(label.clone(), descriptor.clone()),
             ));
+            link_mentions = link_mentions.on_press(GuiMessage::SuggestLinks(Some((
+                label.clone(),
+                descriptor.clone(),
+            ))));
+        }
+        let labels = state.label_view_state.get_multi_selected();
+        let descriptors = state.descriptor_view_state.get_multi_selected();
+        if !descriptors.is_empty() && (labels.len() > 1 || descriptors.len() > 1) {
+            delete_descriptor =
+                button("Delete Selected Descriptors").on_press(GuiMessage::EntityViewUpd(
+                    EntityViewMessage::DeleteDescriptors(labels, descriptors),
+                ));
         }
     }
     Row::new()
         .push(new_descriptor)
         .push(rename_descriptor)
+        .push(rename_everywhere)
         .push(delete_descriptor)
+        .push(link_mentions)
         .spacing(5)
         .padding(5)
 }
 
 fn col_views(state: &EntityViewState) -> Row<'_, GuiMessage> {
-    let mut row = Row::new()
-        .push(db_col_view::widget::new(
-            "Label",
-            |m| GuiMessage::EntityViewUpd(EntityViewMessage::LabelViewUpdate(m)),
-            &state.label_view_state,
-        ))
-        .push(db_col_view::widget::new(
-            "Descriptor",
-            |m| GuiMessage::EntityViewUpd(EntityViewMessage::DescriptorViewUpdate(m)),
-            &state.descriptor_view_state,
-        ));
+    let mut row = Row::new().push(db_col_view::widget::new(
+        "Label",
+        |m| GuiMessage::EntityViewUpd(EntityViewMessage::LabelViewUpdate(m)),
+        &state.label_view_state,
+    ));
+    if state.show_overview {
+        row = row.push(overview(state));
+    } else {
+        row = row
+            .push(db_col_view::widget::new(
+                "Descriptor",
+                |m| GuiMessage::EntityViewUpd(EntityViewMessage::DescriptorViewUpdate(m)),
+                &state.descriptor_view_state,
+            ))
+            .push(description(state));
+    }
+    row.push(
+        Column::new()
+            .push(relationships(state))
+            .push(history_items(state))
+            .push(backlinks(state))
+            .width(Length::Fill),
+    )
+    .align_y(Alignment::Start)
+    .width(Length::Fill)
+    .height(Length::Fill)
+}
+
+fn description(state: &EntityViewState) -> Element<'_, GuiMessage> {
     if state.edit_mode {
-        row = row.push(editor::widget::view(
+        editor::widget::view(
             "Description",
             &state.current_description,
             |a| GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionUpdate(a)),
+            |l| GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionComplete(l)),
             GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionDiscard),
             GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionSave),
-        ));
+        )
+        .into()
     } else {
-        row = row.push(content_view(state.current_description.get_text()));
+        content_view(state.current_description.get_text())
+    }
+}
+
+fn overview(state: &EntityViewState) -> Element<'_, GuiMessage> {
+    let mut sections = Column::new().spacing(10);
+    for (descriptor, description) in state.overview.iter() {
+        let mut title = Row::new()
+            .push(header(descriptor.to_str()))
+            .align_y(Alignment::Center)
+            .spacing(5);
+        if state.edit_mode {
+            title = title.push(button("Edit").on_press(GuiMessage::EntityViewUpd(
+                EntityViewMessage::EditDescriptor(descriptor.clone()),
+            )));
+        }
+        sections = sections
+            .push(title)
+            .push(content_view(description.to_str().to_string()));
     }
-    row.align_y(Alignment::Start)
+    Scrollable::new(sections.padding(5))
         .width(Length::Fill)
         .height(Length::Fill)
+        .into()
+}
+
+fn history_items(state: &EntityViewState) -> Column<'_, GuiMessage> {
+    let mut items = Column::new().spacing(5);
+    for item in state.history_items.iter() {
+        let date = get_dating(&item.properties).notation(&item.year, &item.day);
+        let summary = item.content.to_str().lines().next().unwrap_or_default();
+        let message = GuiMessage::GoToHistoryItem(item.year, item.day, item.timestamp);
+        items = items.push(
+            button(Text::new(format!("{}: {}", date, summary)))
+                .on_press(message)
+                .width(Length::Fill),
+        );
+    }
+    Column::new()
+        .push(header("Participated In"))
+        .push(Scrollable::new(items))
+        .width(Length::Fill)
+        .padding(5)
+        .spacing(5)
+}
+
+fn backlinks(state: &EntityViewState) -> Column<'_, GuiMessage> {
+    let mut links = Column::new().spacing(5);
+    for backlink in state.backlinks.iter() {
+        links = links.push(
+            button(Text::new(format!(
+                "{}: {}",
+                backlink.location, backlink.snippet
+            )))
+            .on_press(backlink.on_press())
+            .width(Length::Fill),
+        );
+    }
+    Column::new()
+        .push(header("Mentioned In"))
+        .push(Scrollable::new(links))
+        .width(Length::Fill)
+        .padding(5)
+        .spacing(5)
+}
+
+fn relationships(state: &EntityViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(header("Relationships"));
+    if state.edit_mode {
+        if let Some(label) = state.get_selected_label() {
+            col = col.push(
+                button("Add Relationship").on_press(GuiMessage::EntityViewUpd(
+                    EntityViewMessage::NewRelationship(label),
+                )),
+            );
+        }
+    }
+    let mut entries = Column::new().spacing(5);
+    entries = entries.push(Text::new("Outgoing:"));
+    for rel in state.outgoing_relationships.iter() {
+        let text = format!("{} -> {}", rel.role, rel.child);
+        entries = entries.push(relationship_entry(state, text, rel.child.to_str(), rel));
+    }
+    entries = entries.push(Text::new("Incoming:"));
+    for rel in state.incoming_relationships.iter() {
+        let text = format!("{} <- {}", rel.role, rel.parent);
+        entries = entries.push(relationship_entry(state, text, rel.parent.to_str(), rel));
+    }
+    col.push(Scrollable::new(entries))
+        .width(Length::Fill)
+        .padding(5)
+        .spacing(5)
+}
+
+fn relationship_entry<'a>(
+    state: &EntityViewState,
+    text: String,
+    other: &str,
+    rel: &EntityRelationship,
+) -> Row<'a, GuiMessage> {
+    let mut row = Row::new().push(
+        button(Text::new(text))
+            .on_press(GuiMessage::GoToEntity(other.into()))
+            .width(Length::Fill),
+    );
+    if state.edit_mode {
+        row = row.push(button("Remove").on_press(GuiMessage::RelationshipViewUpd(
+            RelationshipViewMessage::DeleteRelationship(rel.clone()),
+        )));
+    }
+    row.spacing(5)
 }
@@ -0,0 +1,243 @@
+use lorecore::types::*;
+
+/// An entity placed in a generation row, with the column counted from the left of that row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TreeNode {
+    pub(crate) label: Label,
+    pub(crate) generation: usize,
+    pub(crate) column: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FamilyTreeLayout {
+    pub(crate) nodes: Vec<TreeNode>,
+    /// Pairs of node indices, the parent first.
+    pub(crate) descents: Vec<(usize, usize)>,
+    /// Pairs of node indices of partners.
+    pub(crate) partnerships: Vec<(usize, usize)>,
+}
+
+impl FamilyTreeLayout {
+    pub(crate) fn generation_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| node.generation + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn generation_width(&self, generation: usize) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.generation == generation)
+            .count()
+    }
+
+    pub(crate) fn max_generation_width(&self) -> usize {
+        (0..self.generation_count())
+            .map(|generation| self.generation_width(generation))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Cycles do not prevent a layout, they only stretch it over more generations.
+pub(crate) fn layout(
+    relationships: &[EntityRelationship],
+    parent_roles: &[Role],
+    partner_roles: &[Role],
+) -> FamilyTreeLayout {
+    let edges = |roles: &[Role]| {
+        let mut edges: Vec<(String, String)> = relationships
+            .iter()
+            .filter(|rel| roles.contains(&rel.role) && rel.parent.to_str() != rel.child.to_str())
+            .map(|rel| {
+                (
+                    rel.parent.to_str().to_string(),
+                    rel.child.to_str().to_string(),
+                )
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    };
+    let descent_edges = edges(parent_roles);
+    let partner_edges = edges(partner_roles);
+
+    let mut labels: Vec<String> = descent_edges
+        .iter()
+        .chain(partner_edges.iter())
+        .flat_map(|(a, b)| [a.clone(), b.clone()])
+        .collect();
+    labels.sort();
+    labels.dedup();
+    let index = |label: &str| labels.iter().position(|l| l == label).unwrap_or_default();
+    let descents: Vec<(usize, usize)> = descent_edges
+        .iter()
+        .map(|(parent, child)| (index(parent), index(child)))
+        .collect();
+    let mut partnerships: Vec<(usize, usize)> = partner_edges
+        .iter()
+        .map(|(a, b)| (index(a).min(index(b)), index(a).max(index(b))))
+        .collect();
+    partnerships.sort();
+    partnerships.dedup();
+
+    let generations = assign_generations(labels.len(), &descents, &partnerships);
+    let columns = assign_columns(&labels, &generations, &descents, &partnerships);
+    let nodes = labels
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| TreeNode {
+            label: label.as_str().into(),
+            generation: generations[i],
+            column: columns[i],
+        })
+        .collect();
+    FamilyTreeLayout {
+        nodes,
+        descents,
+        partnerships,
+    }
+}
+
+/// The rounds are bounded, since along a cycle the generations would never settle.
+fn assign_generations(
+    count: usize,
+    descents: &[(usize, usize)],
+    partnerships: &[(usize, usize)],
+) -> Vec<usize> {
+    let mut generations = vec![0; count];
+    for _ in 0..count {
+        let mut changed = false;
+        for &(parent, child) in descents {
+            if generations[child] <= generations[parent] {
+                generations[child] = generations[parent] + 1;
+                changed = true;
+            }
+        }
+        for &(a, b) in partnerships {
+            let generation = generations[a].max(generations[b]);
+            if generations[a] != generation || generations[b] != generation {
+                generations[a] = generation;
+                generations[b] = generation;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    generations
+}
+
+/// Orders each generation by the mean column of its parents, then pulls partners together.
+fn assign_columns(
+    labels: &[String],
+    generations: &[usize],
+    descents: &[(usize, usize)],
+    partnerships: &[(usize, usize)],
+) -> Vec<usize> {
+    let mut columns: Vec<Option<usize>> = vec![None; labels.len()];
+    let generation_count = generations.iter().map(|g| g + 1).max().unwrap_or(0);
+    for generation in 0..generation_count {
+        let mut members: Vec<(f32, usize)> = (0..labels.len())
+            .filter(|&i| generations[i] == generation)
+            .map(|i| {
+                let parent_columns: Vec<usize> = descents
+                    .iter()
+                    .filter(|&&(_, child)| child == i)
+                    .filter_map(|&(parent, _)| columns[parent])
+                    .collect();
+                let key = if parent_columns.is_empty() {
+                    f32::INFINITY
+                } else {
+                    parent_columns.iter().sum::<usize>() as f32 / parent_columns.len() as f32
+                };
+                (key, i)
+            })
+            .collect();
+        members.sort_by(|a, b| a.0.total_cmp(&b.0).then(labels[a.1].cmp(&labels[b.1])));
+
+        let mut row: Vec<usize> = vec![];
+        for &(_, i) in members.iter() {
+            if row.contains(&i) {
+                continue;
+            }
+            row.push(i);
+            for &(_, partner) in members.iter() {
+                let are_partners = partnerships.contains(&(i.min(partner), i.max(partner)));
+                if are_partners && !row.contains(&partner) {
+                    row.push(partner);
+                }
+            }
+        }
+        for (column, i) in row.into_iter().enumerate() {
+            columns[i] = Some(column);
+        }
+    }
+    columns.into_iter().map(Option::unwrap_or_default).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel(parent: &str, child: &str, role: &str) -> EntityRelationship {
+        EntityRelationship {
+            parent: parent.into(),
+            child: child.into(),
+            role: role.into(),
+        }
+    }
+
+    #[test]
+    fn generations_run_top_to_bottom_with_partners_side_by_side() {
+        let relationships = vec![
+            rel("king", "prince", "father"),
+            rel("queen", "prince", "mother"),
+            rel("king", "queen", "spouse"),
+            rel("prince", "heir", "father"),
+            rel("prince", "consort", "spouse"),
+            rel("consort", "heir", "mother"),
+            rel("king", "knight", "liege"),
+            rel("aunt", "zealot", "mother"),
+        ];
+        let parent_roles = vec!["father".into(), "mother".into()];
+        let partner_roles = vec!["spouse".into()];
+        let tree = layout(&relationships, &parent_roles, &partner_roles);
+
+        let node = |label: &str| {
+            tree.nodes
+                .iter()
+                .find(|node| node.label.to_str() == label)
+                .unwrap()
+        };
+        assert!(!tree
+            .nodes
+            .iter()
+            .any(|node| node.label.to_str() == "knight"));
+        assert_eq!(node("king").generation, 0);
+        assert_eq!(node("queen").generation, 0);
+        assert_eq!(node("prince").generation, 1);
+        assert_eq!(node("consort").generation, 1);
+        assert_eq!(node("heir").generation, 2);
+        assert_eq!(node("king").column.abs_diff(node("queen").column), 1);
+        assert_eq!(node("prince").column.abs_diff(node("consort").column), 1);
+        assert_eq!(tree.descents.len(), 5);
+        assert_eq!(tree.partnerships.len(), 2);
+        assert_eq!(tree.generation_count(), 3);
+    }
+
+    #[test]
+    fn cycles_still_produce_a_layout() {
+        let relationships = vec![
+            rel("ouroboros", "tail", "mother"),
+            rel("tail", "ouroboros", "mother"),
+        ];
+        let tree = layout(&relationships, &["mother".into()], &[]);
+        assert_eq!(tree.nodes.len(), 2);
+        assert_eq!(tree.descents.len(), 2);
+    }
+}
@@ -0,0 +1,59 @@
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase, relationship::extract_roles,
+        search_params::RelationshipSearchParams,
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+use self::layout::FamilyTreeLayout;
+
+pub(crate) mod layout;
+pub(crate) mod widget;
+
+#[derive(Debug, Default)]
+pub(super) struct FamilyTreeViewState {
+    pub(super) display_protected: bool,
+    pub(super) role_options: Vec<Role>,
+    pub(super) parent_roles: Vec<Role>,
+    pub(super) partner_roles: Vec<Role>,
+    pub(super) layout: FamilyTreeLayout,
+    pub(super) selected_label: Option<Label>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum FamilyTreeViewMessage {
+    ParentRoleToggled(Role, bool),
+    PartnerRoleToggled(Role, bool),
+    EntitySelected(Label),
+}
+
+impl FamilyTreeViewState {
+    pub(super) fn get_current_relationships(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<EntityRelationship>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let mut relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+        if !self.display_protected {
+            relationships.retain(|rel| {
+                !rel.role.is_protected()
+                    && !Label::from(rel.parent.to_str()).is_protected()
+                    && !Label::from(rel.child.to_str()).is_protected()
+            });
+        }
+        Ok(relationships)
+    }
+
+    pub(super) fn get_role_options(relationships: &[EntityRelationship]) -> Vec<Role> {
+        let mut roles = extract_roles(relationships);
+        roles.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+        roles.dedup();
+        roles
+    }
+}
@@ -0,0 +1,217 @@
+use iced::{
+    mouse,
+    widget::{
+        button,
+        canvas::{self, Event, Frame, Geometry, Path, Stroke},
+        scrollable::{Direction, Scrollbar},
+        Checkbox, Column, Row, Scrollable, Text,
+    },
+    Alignment, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme,
+};
+use lorecore::types::*;
+
+use crate::app::message_handling::GuiMessage;
+
+use super::{layout::FamilyTreeLayout, FamilyTreeViewMessage, FamilyTreeViewState};
+
+const MARGIN: f32 = 20.0;
+const NODE_WIDTH: f32 = 140.0;
+const NODE_HEIGHT: f32 = 30.0;
+const COLUMN_SPACING: f32 = 170.0;
+const GENERATION_SPACING: f32 = 80.0;
+
+pub(crate) fn new(state: &FamilyTreeViewState) -> Element<'_, GuiMessage> {
+    Row::new()
+        .push(Scrollable::new(role_selection(state)))
+        .push(Column::new().push(selection(state)).push(tree(state)))
+        .into()
+}
+
+fn role_selection(state: &FamilyTreeViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(Text::new("Parent roles:"));
+    for role in state.role_options.iter() {
+        let role = role.clone();
+        let checked = state.parent_roles.contains(&role);
+        col = col.push(
+            Checkbox::new(checked)
+                .label(role.to_str().to_string())
+                .on_toggle(move |checked| {
+                    GuiMessage::FamilyTreeViewUpd(FamilyTreeViewMessage::ParentRoleToggled(
+                        role.clone(),
+                        checked,
+                    ))
+                }),
+        );
+    }
+    col = col.push(Text::new("Partner roles:"));
+    for role in state.role_options.iter() {
+        let role = role.clone();
+        let checked = state.partner_roles.contains(&role);
+        col = col.push(
+            Checkbox::new(checked)
+                .label(role.to_str().to_string())
+                .on_toggle(move |checked| {
+                    GuiMessage::FamilyTreeViewUpd(FamilyTreeViewMessage::PartnerRoleToggled(
+                        role.clone(),
+                        checked,
+                    ))
+                }),
+        );
+    }
+    col.spacing(5).padding(5)
+}
+
+fn selection(state: &FamilyTreeViewState) -> Row<'_, GuiMessage> {
+    let row = Row::new().align_y(Alignment::Center).spacing(5).padding(5);
+    match &state.selected_label {
+        Some(label) => row
+            .push(Text::new(label.to_str()))
+            .push(button("Go to Entity").on_press(GuiMessage::GoToEntity(label.clone()))),
+        None if state.layout.nodes.is_empty() => row.push(Text::new(
+            "Check parent and partner roles to draw a family tree.",
+        )),
+        None => row.push(Text::new("Click an entity to select it.")),
+    }
+}
+
+fn tree(state: &FamilyTreeViewState) -> Element<'_, GuiMessage> {
+    let tree = FamilyTree {
+        layout: &state.layout,
+        selected_label: state.selected_label.as_ref(),
+    };
+    let size = tree.size();
+    Scrollable::new(
+        canvas::Canvas::new(tree)
+            .width(size.width)
+            .height(size.height),
+    )
+    .direction(Direction::Both {
+        vertical: Scrollbar::default(),
+        horizontal: Scrollbar::default(),
+    })
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Generations stacked from top to bottom, each row centred above the widest one.
+struct FamilyTree<'a> {
+    layout: &'a FamilyTreeLayout,
+    selected_label: Option<&'a Label>,
+}
+
+impl FamilyTree<'_> {
+    fn size(&self) -> Size {
+        let columns = self.layout.max_generation_width() as f32;
+        let generations = self.layout.generation_count() as f32;
+        Size::new(
+            2.0 * MARGIN + columns * COLUMN_SPACING,
+            2.0 * MARGIN + generations * GENERATION_SPACING,
+        )
+    }
+
+    fn node_bounds(&self, index: usize) -> Rectangle {
+        let node = &self.layout.nodes[index];
+        let missing_columns =
+            self.layout.max_generation_width() - self.layout.generation_width(node.generation);
+        let x = MARGIN
+            + (missing_columns as f32 / 2.0 + node.column as f32) * COLUMN_SPACING
+            + (COLUMN_SPACING - NODE_WIDTH) / 2.0;
+        let y = MARGIN + node.generation as f32 * GENERATION_SPACING;
+        Rectangle::new(Point::new(x, y), Size::new(NODE_WIDTH, NODE_HEIGHT))
+    }
+
+    fn node_at(&self, position: Point) -> Option<usize> {
+        (0..self.layout.nodes.len()).find(|&i| self.node_bounds(i).contains(position))
+    }
+}
+
+impl canvas::Program<GuiMessage> for FamilyTree<'_> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut (),
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<GuiMessage>> {
+        let position = cursor.position_in(bounds)?;
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let index = self.node_at(position)?;
+                let label = self.layout.nodes[index].label.clone();
+                let message =
+                    GuiMessage::FamilyTreeViewUpd(FamilyTreeViewMessage::EntitySelected(label));
+                Some(canvas::Action::publish(message).and_capture())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+
+        for &(parent, child) in self.layout.descents.iter() {
+            let from = self.node_bounds(parent);
+            let to = self.node_bounds(child);
+            let start = Point::new(from.center_x(), from.y + from.height);
+            let end = Point::new(to.center_x(), to.y);
+            let middle_y = end.y - (GENERATION_SPACING - NODE_HEIGHT) / 2.0;
+            let line = Path::new(|builder| {
+                builder.move_to(start);
+                builder.line_to(Point::new(start.x, middle_y));
+                builder.line_to(Point::new(end.x, middle_y));
+                builder.line_to(end);
+            });
+            frame.stroke(&line, Stroke::default().with_color(palette.text));
+        }
+
+        for &(a, b) in self.layout.partnerships.iter() {
+            let (left, right) = (self.node_bounds(a), self.node_bounds(b));
+            let (left, right) = if left.x <= right.x {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            let y = left.center_y();
+            let line = Path::line(Point::new(left.x + left.width, y), Point::new(right.x, y));
+            frame.stroke(
+                &line,
+                Stroke::default()
+                    .with_color(palette.success)
+                    .with_width(3.0),
+            );
+        }
+
+        for (i, node) in self.layout.nodes.iter().enumerate() {
+            let node_bounds = self.node_bounds(i);
+            let is_selected = self.selected_label == Some(&node.label);
+            let fill = if is_selected {
+                palette.primary
+            } else {
+                Color {
+                    a: 0.2,
+                    ..palette.primary
+                }
+            };
+            frame.fill_rectangle(node_bounds.position(), node_bounds.size(), fill);
+            frame.fill_text(canvas::Text {
+                content: node.label.to_str().to_string(),
+                position: Point::new(node_bounds.x + 5.0, node_bounds.y + 8.0),
+                color: palette.text,
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
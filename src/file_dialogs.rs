@@ -18,3 +18,11 @@ pub(super) fn open() -> Option<PathBuf> {
         .set_directory(current_path())
         .pick_file()
 }
+
+pub(super) fn export_csv(file_name: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("Comma-separated values (.csv)", &["csv"])
+        .set_file_name(file_name)
+        .set_directory(current_path())
+        .save_file()
+}
@@ -0,0 +1,157 @@
+use std::fmt::Display;
+
+use lorecore::types::*;
+
+use crate::errors::LoreGuiError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum DatePrecision {
+    #[default]
+    Exact,
+    Decade,
+    Century,
+}
+
+impl DatePrecision {
+    pub(crate) const ALL: [DatePrecision; 3] = [
+        DatePrecision::Exact,
+        DatePrecision::Decade,
+        DatePrecision::Century,
+    ];
+
+    fn years(&self) -> i32 {
+        match self {
+            DatePrecision::Exact => 1,
+            DatePrecision::Decade => 10,
+            DatePrecision::Century => 100,
+        }
+    }
+
+    pub(crate) fn to_str(self) -> &'static str {
+        match self {
+            DatePrecision::Exact => "exact",
+            DatePrecision::Decade => "decade",
+            DatePrecision::Century => "century",
+        }
+    }
+
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        DatePrecision::ALL
+            .into_iter()
+            .find(|precision| precision.to_str() == text)
+    }
+}
+
+impl Display for DatePrecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_str().fmt(f)
+    }
+}
+
+/// How certain the date of a history item is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Dating {
+    pub(crate) circa: bool,
+    pub(crate) range_end: Option<Year>,
+    pub(crate) precision: DatePrecision,
+}
+
+impl Dating {
+    /// Circa dates are widened by a decade, or by a century for dates of century precision.
+    fn circa_tolerance(&self) -> i32 {
+        match self.precision {
+            DatePrecision::Century => 100,
+            _ => 10,
+        }
+    }
+
+    /// The year rounded down to the start of its decade or century.
+    fn floor(&self, year: i32) -> i32 {
+        year.div_euclid(self.precision.years()) * self.precision.years()
+    }
+
+    pub(crate) fn validate(&self, year: &Year) -> Result<(), LoreGuiError> {
+        match self.range_end {
+            Some(end) if end.to_int() < year.to_int() => Err(LoreGuiError::InputError(
+                "A date range cannot end before it starts.".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The first and last year the dated item may fall into.
+    pub(crate) fn span(&self, year: &Year) -> (i32, i32) {
+        let mut start = self.floor(year.to_int());
+        let end = self
+            .range_end
+            .map(|end| end.to_int())
+            .unwrap_or(year.to_int());
+        let mut end = self.floor(end.max(year.to_int())) + self.precision.years() - 1;
+        if self.circa {
+            start -= self.circa_tolerance();
+            end += self.circa_tolerance();
+        }
+        (start, end)
+    }
+
+    pub(crate) fn notation(&self, year: &Year, day: &Day) -> String {
+        let mut notation = match self.precision {
+            DatePrecision::Exact if *day != Day::NONE => format!("{}, day {}", year, day),
+            DatePrecision::Exact => year.to_string(),
+            DatePrecision::Decade => format!("{}s", self.floor(year.to_int())),
+            DatePrecision::Century => format!("{}s (century)", self.floor(year.to_int())),
+        };
+        if let Some(end) = self.range_end {
+            let end = match self.precision {
+                DatePrecision::Exact => end.to_string(),
+                _ => format!("{}s", self.floor(end.to_int())),
+            };
+            notation = format!("{} to {}", notation, end);
+        }
+        if self.circa {
+            notation = format!("c. {}", notation);
+        }
+        notation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circa_range_widens_span() {
+        let dating = Dating {
+            circa: true,
+            range_end: Some(140.into()),
+            precision: DatePrecision::Exact,
+        };
+        assert_eq!(dating.span(&120.into()), (110, 150));
+        assert_eq!(dating.notation(&120.into(), &Day::NONE), "c. 120 to 140");
+    }
+
+    #[test]
+    fn imprecise_years_are_rounded_down() {
+        let decade = Dating {
+            precision: DatePrecision::Decade,
+            ..Default::default()
+        };
+        assert_eq!(decade.span(&1234.into()), (1230, 1239));
+        assert_eq!(decade.notation(&1234.into(), &Day::NONE), "1230s");
+        let century = Dating {
+            precision: DatePrecision::Century,
+            ..Default::default()
+        };
+        assert_eq!(century.span(&(-150).into()), (-200, -101));
+    }
+
+    #[test]
+    fn ranges_ending_before_they_start_are_rejected() {
+        let dating = Dating {
+            range_end: Some(100.into()),
+            ..Default::default()
+        };
+        assert!(dating.validate(&120.into()).is_err());
+        assert!(dating.validate(&80.into()).is_ok());
+    }
+}
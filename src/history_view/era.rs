@@ -0,0 +1,91 @@
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+const ERA_LABEL: &str = "_eras";
+const RANGE_SEPARATOR: &str = " to ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Era {
+    pub(crate) name: String,
+    pub(crate) start: Year,
+    pub(crate) end: Year,
+}
+
+impl Era {
+    pub(crate) fn contains(&self, year: &Year) -> bool {
+        self.start.to_int() <= year.to_int() && year.to_int() <= self.end.to_int()
+    }
+
+    pub(crate) fn overlaps(&self, start: i32, end: i32) -> bool {
+        self.start.to_int() <= end && start <= self.end.to_int()
+    }
+
+    pub(crate) fn to_column(&self) -> EntityColumn {
+        EntityColumn {
+            label: ERA_LABEL.into(),
+            descriptor: self.name.clone().into(),
+            description: format!("{}{}{}", self.start, RANGE_SEPARATOR, self.end).into(),
+        }
+    }
+
+    fn from_column(col: &EntityColumn) -> Result<Self, LoreGuiError> {
+        let range = col.description.to_str();
+        let (start, end) = range.split_once(RANGE_SEPARATOR).ok_or_else(|| {
+            LoreGuiError::InputError(format!("Cannot parse year range of era: {}", range))
+        })?;
+        Ok(Era {
+            name: col.descriptor.to_str().to_string(),
+            start: Year::try_from(start.to_string())?,
+            end: Year::try_from(end.to_string())?,
+        })
+    }
+}
+
+impl std::fmt::Display for Era {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}{}{}",
+            self.name, self.start, RANGE_SEPARATOR, self.end
+        )
+    }
+}
+
+pub(crate) fn era_key(name: &str) -> (Label, Descriptor) {
+    (ERA_LABEL.into(), name.into())
+}
+
+pub(crate) fn read_eras(db: &LoreDatabase) -> Result<Vec<Era>, LoreGuiError> {
+    let search_params = EntityColumnSearchParams::new(Some(SqlSearchText::exact(ERA_LABEL)), None);
+    let mut eras = db
+        .read_entity_columns(search_params)?
+        .iter()
+        .map(Era::from_column)
+        .collect::<Result<Vec<Era>, LoreGuiError>>()?;
+    eras.sort_by_key(|era| (era.start.to_int(), era.end.to_int()));
+    Ok(eras)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn era_survives_roundtrip_through_entity_column() {
+        let era = Era {
+            name: "Age of Dragons".to_string(),
+            start: (-3000).into(),
+            end: (-1200).into(),
+        };
+        let column = era.to_column();
+        assert_eq!(column.description.to_str(), "-3000 to -1200");
+        assert_eq!(Era::from_column(&column).unwrap(), era);
+    }
+}
@@ -1,4 +1,6 @@
-use iced::widget::text_editor;
+use std::ops::Range;
+
+use iced::widget::{combo_box, text_editor};
 use lorecore::{
     extractions::{extract_days, extract_years},
     sql::{lore_database::LoreDatabase, search_params::HistoryItemSearchParams},
@@ -7,13 +9,20 @@ use lorecore::{
 
 use crate::{
     db_col_view::{entry::DbColViewEntry, ColViewMes},
-    dialog::redate_history::RedateHistoryData,
+    dialog::{redate_history::RedateHistoryData, redate_history_items::RedateHistoryItemsData},
     editor::EditorState,
     errors::LoreGuiError,
 };
 
+use self::dating::Dating;
+use self::era::Era;
+use self::properties::{get_dating, read_history_item};
 use super::db_col_view::state::DbColViewState;
 
+pub(crate) mod dating;
+pub(crate) mod era;
+pub(crate) mod properties;
+mod timeline;
 pub(crate) mod widget;
 
 #[derive(Debug, Default)]
@@ -23,6 +32,11 @@ pub(super) struct HistoryViewState {
     pub(super) day_view_state: DbColViewState<Day>,
     pub(super) timestamp_view_state: DbColViewState<Timestamp>,
     pub(super) current_content: EditorState,
+    pub(super) eras: Vec<Era>,
+    pub(super) era_filter: Option<Era>,
+    pub(super) participants: Vec<Label>,
+    pub(super) dating: Dating,
+    pub(super) participant_picker: combo_box::State<Label>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,12 +44,20 @@ pub(super) enum HistoryViewMessage {
     NewHistoryItem,
     RedateHistoryItem(RedateHistoryData),
     DeleteHistoryItem(Timestamp),
+    RedateHistoryItems(RedateHistoryItemsData),
+    DeleteHistoryItems(Vec<Timestamp>),
     YearViewUpdate(ColViewMes<Year>),
     DayViewUpdate(ColViewMes<Day>),
     HistoryTimestampViewUpdate(ColViewMes<Timestamp>),
     ContentUpdate(text_editor::Action),
+    ContentComplete(Label),
     ContentDiscard,
     ContentSave,
+    NewEra,
+    DeleteEra(String),
+    EraFilterSelected(Option<Era>),
+    AddParticipant(Label),
+    RemoveParticipant(Label),
 }
 
 impl HistoryViewState {
@@ -47,13 +69,42 @@ impl HistoryViewState {
             Some(db) => db,
             None => return Ok(vec![]),
         };
-        let year = self.year_view_state.get_search_int()?.map(|y| y.into());
-        let search_params = HistoryItemSearchParams::new(year, None, None, None);
-        let history_items = db.read_history_items(search_params)?;
+        let search_year = self.year_view_state.get_search_int()?;
+        let search_params = HistoryItemSearchParams::new(None, None, None, None);
+        let mut history_items = db.read_history_items(search_params)?;
+        if let Some(search_year) = search_year {
+            history_items.retain(|item| {
+                let (start, end) = get_dating(&item.properties).span(&item.year);
+                (start..=end).contains(&search_year)
+            });
+        }
+        if let Some(era) = &self.era_filter {
+            history_items.retain(|item| {
+                let (start, end) = get_dating(&item.properties).span(&item.year);
+                era.overlaps(start, end)
+            });
+        }
         let years = extract_years(&history_items);
         Ok(years)
     }
 
+    pub(super) fn get_era_of_year(&self, year: &Year) -> Option<&Era> {
+        self.eras.iter().find(|era| era.contains(year))
+    }
+
+    /// Splits the listed years into consecutive runs belonging to the same era.
+    pub(super) fn get_year_groups(&self) -> Vec<(Option<&Era>, Range<usize>)> {
+        let mut groups: Vec<(Option<&Era>, Range<usize>)> = Vec::new();
+        for (i, entry) in self.year_view_state.get_entries().iter().enumerate() {
+            let era = entry.0.as_ref().and_then(|year| self.get_era_of_year(year));
+            match groups.last_mut() {
+                Some((last_era, range)) if *last_era == era => range.end = i + 1,
+                _ => groups.push((era, i..i + 1)),
+            }
+        }
+        groups
+    }
+
     pub(super) fn get_current_days(
         &self,
         db: &Option<LoreDatabase>,
@@ -121,6 +172,35 @@ impl HistoryViewState {
         Ok(content)
     }
 
+    pub(super) fn get_current_properties(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<HistoryItemProperties, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(HistoryItemProperties::none()),
+        };
+        let timestamp = match self.get_selected_timestamp() {
+            Some(timestamp) => timestamp,
+            None => return Ok(HistoryItemProperties::none()),
+        };
+        let properties = match read_history_item(db, timestamp)? {
+            Some(item) => item.properties,
+            None => HistoryItemProperties::none(),
+        };
+        Ok(properties)
+    }
+
+    /// The labels offered for autocompletion that do not participate yet.
+    pub(super) fn get_participant_options(&self) -> Vec<Label> {
+        self.current_content
+            .get_label_options()
+            .iter()
+            .map(|option| option.label.clone())
+            .filter(|label| !self.participants.contains(label))
+            .collect()
+    }
+
     pub(super) fn get_selected_year(&self) -> Option<Year> {
         self.year_view_state.get_selected().0
     }
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use lorecore::{
+    sql::{lore_database::LoreDatabase, search_params::HistoryItemSearchParams},
+    types::*,
+};
+use serde_json::Value;
+
+use crate::errors::LoreGuiError;
+
+use super::dating::{DatePrecision, Dating};
+
+const PARTICIPANTS_KEY: &str = "participants";
+const CIRCA_KEY: &str = "circa";
+const RANGE_END_KEY: &str = "range_end";
+const PRECISION_KEY: &str = "precision";
+
+fn to_map(properties: &HistoryItemProperties) -> HashMap<String, Value> {
+    HashMap::from(properties.clone())
+}
+
+pub(crate) fn get_participants(properties: &HistoryItemProperties) -> Vec<Label> {
+    match to_map(properties).get(PARTICIPANTS_KEY) {
+        Some(Value::Array(participants)) => participants
+            .iter()
+            .filter_map(|p| p.as_str())
+            .map(Label::from)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+pub(crate) fn with_participants(
+    properties: &HistoryItemProperties,
+    participants: &[Label],
+) -> HistoryItemProperties {
+    let mut map = to_map(properties);
+    if participants.is_empty() {
+        map.remove(PARTICIPANTS_KEY);
+    } else {
+        let participants = participants
+            .iter()
+            .map(|p| Value::String(p.to_str().to_string()))
+            .collect();
+        map.insert(PARTICIPANTS_KEY.to_string(), Value::Array(participants));
+    }
+    map.into()
+}
+
+pub(crate) fn get_dating(properties: &HistoryItemProperties) -> Dating {
+    let map = to_map(properties);
+    let circa = map
+        .get(CIRCA_KEY)
+        .and_then(|c| c.as_bool())
+        .unwrap_or(false);
+    let range_end = map
+        .get(RANGE_END_KEY)
+        .and_then(|end| end.as_i64())
+        .map(|end| Year::from(end as i32));
+    let precision = map
+        .get(PRECISION_KEY)
+        .and_then(|p| p.as_str())
+        .and_then(DatePrecision::parse)
+        .unwrap_or_default();
+    Dating {
+        circa,
+        range_end,
+        precision,
+    }
+}
+
+pub(crate) fn with_dating(
+    properties: &HistoryItemProperties,
+    dating: &Dating,
+) -> HistoryItemProperties {
+    let mut map = to_map(properties);
+    map.remove(CIRCA_KEY);
+    map.remove(RANGE_END_KEY);
+    map.remove(PRECISION_KEY);
+    if dating.circa {
+        map.insert(CIRCA_KEY.to_string(), Value::Bool(true));
+    }
+    if let Some(end) = dating.range_end {
+        map.insert(RANGE_END_KEY.to_string(), Value::from(end.to_int()));
+    }
+    if dating.precision != DatePrecision::Exact {
+        let precision = Value::String(dating.precision.to_str().to_string());
+        map.insert(PRECISION_KEY.to_string(), precision);
+    }
+    map.into()
+}
+
+pub(crate) fn read_history_item(
+    db: &LoreDatabase,
+    timestamp: Timestamp,
+) -> Result<Option<HistoryItem>, LoreGuiError> {
+    let search_params = HistoryItemSearchParams::new(None, None, Some(timestamp), None);
+    let history_items = db.read_history_items(search_params)?;
+    if history_items.len() > 1 {
+        return Err(LoreGuiError::MultipleResults);
+    }
+    Ok(history_items.into_iter().next())
+}
+
+/// Rewrites the item under its timestamp, restoring the original should the write fail.
+pub(crate) fn change_history_item_properties(
+    db: &LoreDatabase,
+    timestamp: Timestamp,
+    properties: HistoryItemProperties,
+) -> Result<(), LoreGuiError> {
+    let item = read_history_item(db, timestamp)?.ok_or_else(|| {
+        LoreGuiError::InputError(format!("No history item with timestamp {}.", timestamp))
+    })?;
+    db.delete_history_item(timestamp)?;
+    let changed = HistoryItem {
+        properties,
+        ..item.clone()
+    };
+    if let Err(e) = db.write_history_items(vec![changed]) {
+        if let Err(restore_error) = db.write_history_items(vec![item]) {
+            return Err(LoreGuiError::InputError(format!(
+                "Changing history item {} failed: {}\nRestoring it failed as well: {}",
+                timestamp, e, restore_error
+            )));
+        }
+        return Err(e.into());
+    }
+    Ok(())
+}
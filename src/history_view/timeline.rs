@@ -0,0 +1,106 @@
+use iced::{
+    mouse,
+    widget::canvas::{self, Frame, Geometry, Path, Stroke, Text},
+    Color, Point, Rectangle, Renderer, Size, Theme,
+};
+
+use crate::app::message_handling::GuiMessage;
+
+use super::era::Era;
+
+const MARGIN: f32 = 10.0;
+const TICK_HEIGHT: f32 = 20.0;
+
+/// A horizontal timeline marking the years with history items, on top of shaded era bands.
+pub(super) struct Timeline<'a> {
+    pub(super) years: Vec<i32>,
+    pub(super) selected_year: Option<i32>,
+    pub(super) eras: &'a [Era],
+}
+
+impl Timeline<'_> {
+    fn year_range(&self) -> Option<(i32, i32)> {
+        let era_bounds = self
+            .eras
+            .iter()
+            .flat_map(|era| [era.start.to_int(), era.end.to_int()]);
+        let all_years = self.years.iter().copied().chain(era_bounds);
+        let min = all_years.clone().min()?;
+        let max = all_years.max()?;
+        Some((min, max))
+    }
+}
+
+impl canvas::Program<GuiMessage> for Timeline<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let (min, max) = match self.year_range() {
+            Some(range) => range,
+            None => return vec![frame.into_geometry()],
+        };
+        let palette = theme.palette();
+        let usable_width = bounds.width - 2.0 * MARGIN;
+        let x_pos = |year: i32| {
+            if max == min {
+                bounds.width / 2.0
+            } else {
+                MARGIN + (year - min) as f32 / (max - min) as f32 * usable_width
+            }
+        };
+
+        let band_colors = [palette.primary, palette.success, palette.warning];
+        for (i, era) in self.eras.iter().enumerate() {
+            let start = x_pos(era.start.to_int());
+            let end = x_pos(era.end.to_int());
+            let color = Color {
+                a: 0.2,
+                ..band_colors[i % band_colors.len()]
+            };
+            frame.fill_rectangle(
+                Point::new(start, 0.0),
+                Size::new((end - start).max(1.0), bounds.height),
+                color,
+            );
+            frame.fill_text(Text {
+                content: era.name.clone(),
+                position: Point::new(start + 2.0, 2.0),
+                color: palette.text,
+                ..Text::default()
+            });
+        }
+
+        let axis_y = bounds.height - TICK_HEIGHT / 2.0;
+        let axis = Path::line(
+            Point::new(MARGIN, axis_y),
+            Point::new(bounds.width - MARGIN, axis_y),
+        );
+        frame.stroke(&axis, Stroke::default().with_color(palette.text));
+
+        for year in self.years.iter() {
+            let x = x_pos(*year);
+            let tick = Path::line(
+                Point::new(x, bounds.height - TICK_HEIGHT),
+                Point::new(x, bounds.height),
+            );
+            let stroke = if Some(*year) == self.selected_year {
+                Stroke::default()
+                    .with_color(palette.primary)
+                    .with_width(3.0)
+            } else {
+                Stroke::default().with_color(palette.text)
+            };
+            frame.stroke(&tick, stroke);
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
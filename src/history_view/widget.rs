@@ -1,15 +1,19 @@
-use iced::widget::button;
+use iced::widget::{button, canvas, combo_box, Button, Text};
 use iced::Alignment;
 use iced::{
     widget::{Column, Row},
     Element, Length,
 };
+use lorecore::types::Timestamp;
 
 use crate::app::message_handling::GuiMessage;
 use crate::content::content_view;
 use crate::dialog::redate_history::RedateHistoryData;
+use crate::dialog::redate_history_items::RedateHistoryItemsData;
+use crate::style::header;
 use crate::{db_col_view, editor};
 
+use super::timeline::Timeline;
 use super::{HistoryViewMessage, HistoryViewState};
 
 pub(crate) fn new(state: &HistoryViewState) -> Element<'_, GuiMessage> {
@@ -17,13 +21,20 @@ pub(crate) fn new(state: &HistoryViewState) -> Element<'_, GuiMessage> {
     if state.edit_mode {
         col = col.push(buttons(state))
     }
-    col.push(col_views(state)).into()
+    col.push(era_filter(state))
+        .push(timeline(state))
+        .push(col_views(state))
+        .into()
 }
 
 fn buttons(state: &HistoryViewState) -> Row<'_, GuiMessage> {
     let new_item = button("New History Item").on_press(GuiMessage::HistoryViewUpd(
         HistoryViewMessage::NewHistoryItem,
     ));
+    let timestamps = state.timestamp_view_state.get_multi_selected();
+    if timestamps.len() > 1 {
+        return bulk_buttons(state, new_item, timestamps);
+    }
     let mut redate_history = button("Redate History Item");
     let mut delete_item = button("Delete History Item");
     if let (Some(timestamp), Some(year), day) = (
@@ -32,7 +43,8 @@ fn buttons(state: &HistoryViewState) -> Row<'_, GuiMessage> {
         state.day_view_state.get_selected(),
     ) {
         let day = day.clone().flatten();
-        let redate_history_data = RedateHistoryData::new(timestamp, year, day);
+        let mut redate_history_data = RedateHistoryData::new(timestamp, year, day);
+        redate_history_data.set_dating(state.dating.clone());
         redate_history = redate_history.on_press(GuiMessage::HistoryViewUpd(
             HistoryViewMessage::RedateHistoryItem(redate_history_data),
         ));
@@ -44,39 +56,167 @@ fn buttons(state: &HistoryViewState) -> Row<'_, GuiMessage> {
         .push(new_item)
         .push(redate_history)
         .push(delete_item)
+        .push(era_buttons(state))
         .spacing(5)
         .padding(5)
 }
 
-fn col_views(state: &HistoryViewState) -> Row<'_, GuiMessage> {
-    let mut row = Row::new()
-        .push(db_col_view::widget::new(
-            "Year",
-            |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::YearViewUpdate(m)),
-            &state.year_view_state,
-        ))
-        .push(db_col_view::widget::new(
-            "Day",
-            |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::DayViewUpdate(m)),
-            &state.day_view_state,
-        ))
-        .push(db_col_view::widget::new(
-            "Timestamp",
-            |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::HistoryTimestampViewUpdate(m)),
-            &state.timestamp_view_state,
+fn bulk_buttons<'a>(
+    state: &'a HistoryViewState,
+    new_item: Button<'a, GuiMessage>,
+    timestamps: Vec<Timestamp>,
+) -> Row<'a, GuiMessage> {
+    let count = timestamps.len();
+    let mut redate_items = button(Text::new(format!("Redate {} History Items", count)));
+    if let Some(year) = state.get_selected_year() {
+        let day = state.get_selected_day();
+        let data = RedateHistoryItemsData::new(timestamps.clone(), year, day);
+        redate_items = redate_items.on_press(GuiMessage::HistoryViewUpd(
+            HistoryViewMessage::RedateHistoryItems(data),
         ));
+    }
+    let delete_items = button(Text::new(format!("Delete {} History Items", count))).on_press(
+        GuiMessage::HistoryViewUpd(HistoryViewMessage::DeleteHistoryItems(timestamps)),
+    );
+    Row::new()
+        .push(new_item)
+        .push(redate_items)
+        .push(delete_items)
+        .push(era_buttons(state))
+        .spacing(5)
+        .padding(5)
+}
+
+fn era_buttons(state: &HistoryViewState) -> Row<'_, GuiMessage> {
+    let new_era =
+        button("New Era").on_press(GuiMessage::HistoryViewUpd(HistoryViewMessage::NewEra));
+    let mut delete_era = button("Delete Era");
+    if let Some(era) = &state.era_filter {
+        delete_era = delete_era.on_press(GuiMessage::HistoryViewUpd(
+            HistoryViewMessage::DeleteEra(era.name.clone()),
+        ));
+    }
+    Row::new().push(new_era).push(delete_era).spacing(5)
+}
+
+fn era_filter(state: &HistoryViewState) -> Row<'_, GuiMessage> {
+    let mut row = Row::new();
+    match &state.era_filter {
+        Some(era) => {
+            row = row.push(Text::new(format!("Showing era {}", era))).push(
+                button("Show All Eras").on_press(GuiMessage::HistoryViewUpd(
+                    HistoryViewMessage::EraFilterSelected(None),
+                )),
+            );
+        }
+        None => row = row.push(Text::new("Showing all eras")),
+    }
+    row.align_y(Alignment::Center).spacing(5).padding(5)
+}
+
+fn timeline(state: &HistoryViewState) -> Element<'_, GuiMessage> {
+    let years = state
+        .year_view_state
+        .get_entries()
+        .iter()
+        .filter_map(|entry| entry.0.map(|year| year.to_int()))
+        .collect();
+    let eras = match &state.era_filter {
+        Some(era) => std::slice::from_ref(era),
+        None => &state.eras,
+    };
+    let timeline = Timeline {
+        years,
+        selected_year: state.get_selected_year().map(|year| year.to_int()),
+        eras,
+    };
+    canvas(timeline).width(Length::Fill).height(60).into()
+}
+
+fn year_col_view(state: &HistoryViewState) -> Element<'_, GuiMessage> {
+    let groups = state
+        .get_year_groups()
+        .into_iter()
+        .map(|(era, range)| {
+            let header: Element<'_, GuiMessage> = match era {
+                Some(era) => button(Text::new(era.to_string()))
+                    .on_press(GuiMessage::HistoryViewUpd(
+                        HistoryViewMessage::EraFilterSelected(Some(era.clone())),
+                    ))
+                    .into(),
+                None => Text::new("[no era]").into(),
+            };
+            (header, range)
+        })
+        .collect();
+    db_col_view::widget::new_grouped(
+        "Year",
+        |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::YearViewUpdate(m)),
+        &state.year_view_state,
+        groups,
+    )
+}
+
+fn col_views(state: &HistoryViewState) -> Row<'_, GuiMessage> {
+    let mut content_col = Column::new();
+    if let (Some(year), Some(_)) = (state.get_selected_year(), state.get_selected_timestamp()) {
+        let day = state.day_view_state.get_selected().clone().flatten();
+        let date = state.dating.notation(&year, &day);
+        content_col = content_col.push(Text::new(format!("Date: {}", date)));
+    }
     if state.edit_mode {
-        row = row.push(editor::widget::view(
+        content_col = content_col.push(editor::widget::view(
             "Content",
             &state.current_content,
             |a| GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentUpdate(a)),
+            |l| GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentComplete(l)),
             GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentDiscard),
             GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave),
         ));
     } else {
-        row = row.push(content_view(state.current_content.get_text()));
+        content_col = content_col.push(content_view(state.current_content.get_text()));
     }
-    row.align_y(Alignment::Start)
+    content_col = content_col.push(participants(state));
+    Row::new()
+        .push(year_col_view(state))
+        .push(db_col_view::widget::new(
+            "Day",
+            |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::DayViewUpdate(m)),
+            &state.day_view_state,
+        ))
+        .push(db_col_view::widget::new(
+            "Timestamp",
+            |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::HistoryTimestampViewUpdate(m)),
+            &state.timestamp_view_state,
+        ))
+        .push(content_col.width(Length::Fill))
+        .align_y(Alignment::Start)
         .width(Length::Fill)
         .height(Length::Fill)
 }
+
+fn participants(state: &HistoryViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(header("Participants"));
+    if state.get_selected_timestamp().is_none() {
+        return col.padding(5).spacing(5);
+    }
+    for label in state.participants.iter() {
+        let mut row = Row::new().push(Text::new(label.to_string()));
+        if state.edit_mode {
+            row = row.push(button("Remove").on_press(GuiMessage::HistoryViewUpd(
+                HistoryViewMessage::RemoveParticipant(label.clone()),
+            )));
+        }
+        col = col.push(row.align_y(Alignment::Center).spacing(5));
+    }
+    if state.edit_mode {
+        let picker = combo_box(
+            &state.participant_picker,
+            "Type to add participant...",
+            None,
+            |label| GuiMessage::HistoryViewUpd(HistoryViewMessage::AddParticipant(label)),
+        );
+        col = col.push(picker);
+    }
+    col.padding(5).spacing(5)
+}
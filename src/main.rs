@@ -9,15 +9,21 @@ use crate::user_preferences::store_database_path;
 
 mod app;
 mod content;
+mod csv;
 mod db_col_view;
 mod dialog;
 mod editor;
 mod entity_view;
 mod errors;
+mod family_tree_view;
 mod file_dialogs;
 mod history_view;
+mod matrix_view;
 mod relationship_view;
+mod report_view;
+mod statistics_view;
 mod style;
+mod table_view;
 mod user_preferences;
 
 const APP_TITLE: &str = "Lore GUI";
@@ -48,6 +54,7 @@ fn main() -> iced::Result {
         .title(APP_TITLE)
         .antialiasing(true)
         .theme(GuiState::theme)
+        .subscription(GuiState::subscription)
         .window(window_settings)
         .run()
 }
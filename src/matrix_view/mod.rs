@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use lorecore::{
+    extractions::extract_labels,
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, RelationshipSearchParams},
+    },
+    types::*,
+};
+
+use crate::{
+    entity_view::category::{read_label_categories, CategoryFilter},
+    errors::LoreGuiError,
+};
+
+pub(crate) mod widget;
+
+#[derive(Debug, Default)]
+pub(super) struct MatrixViewState {
+    pub(super) display_protected: bool,
+    pub(super) edit_mode: bool,
+    pub(super) category_options: Vec<CategoryFilter>,
+    pub(super) row_filter: CategoryFilter,
+    pub(super) column_filter: CategoryFilter,
+    pub(super) parents: Vec<Label>,
+    pub(super) children: Vec<Label>,
+    /// The roles of the relationships between each parent and child.
+    pub(super) cell_roles: HashMap<(String, String), Vec<Role>>,
+    pub(super) selected_cell: Option<(Parent, Child)>,
+    pub(super) cell_text: String,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum MatrixViewMessage {
+    RowFilterSelected(CategoryFilter),
+    ColumnFilterSelected(CategoryFilter),
+    CellSelected(Parent, Child),
+    CellTextUpdate(String),
+    CellSave,
+    ExportCsv,
+}
+
+impl MatrixViewState {
+    /// The labels of the rows and the columns, respectively.
+    pub(super) fn get_current_labels(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<(Vec<Label>, Vec<Label>), LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok((vec![], vec![])),
+        };
+        let entity_columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+        let mut labels = extract_labels(&entity_columns);
+        if !self.display_protected {
+            labels.retain(|label| !label.is_protected());
+        }
+        labels.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+        let label_categories = read_label_categories(db)?;
+        let filtered = |filter: &CategoryFilter| {
+            labels
+                .iter()
+                .filter(|label| filter.matches(label, &label_categories))
+                .cloned()
+                .collect()
+        };
+        Ok((filtered(&self.row_filter), filtered(&self.column_filter)))
+    }
+
+    pub(super) fn get_current_cell_roles(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<HashMap<(String, String), Vec<Role>>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(HashMap::new()),
+        };
+        let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+        let mut cell_roles: HashMap<(String, String), Vec<Role>> = HashMap::new();
+        for rel in relationships {
+            if !self.display_protected && rel.role.is_protected() {
+                continue;
+            }
+            let cell = (
+                rel.parent.to_str().to_string(),
+                rel.child.to_str().to_string(),
+            );
+            cell_roles.entry(cell).or_default().push(rel.role);
+        }
+        Ok(cell_roles)
+    }
+
+    pub(super) fn get_roles(&self, parent: &str, child: &str) -> Vec<Role> {
+        self.cell_roles
+            .get(&(parent.to_string(), child.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub(super) fn get_cell_text(&self, parent: &str, child: &str) -> String {
+        self.get_roles(parent, child)
+            .iter()
+            .map(|role| role.to_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The matrix as it is displayed, with the child labels as header row.
+    pub(super) fn to_rows(&self) -> Vec<Vec<String>> {
+        let header = std::iter::once(String::new())
+            .chain(self.children.iter().map(|c| c.to_str().to_string()))
+            .collect();
+        let rows = self.parents.iter().map(|parent| {
+            std::iter::once(parent.to_str().to_string())
+                .chain(
+                    self.children
+                        .iter()
+                        .map(|child| self.get_cell_text(parent.to_str(), child.to_str())),
+                )
+                .collect()
+        });
+        std::iter::once(header).chain(rows).collect()
+    }
+}
@@ -0,0 +1,128 @@
+use iced::{
+    widget::{
+        button,
+        scrollable::{Direction, Scrollbar},
+        Column, PickList, Row, Scrollable, Text, TextInput,
+    },
+    Alignment, Element, Length,
+};
+use lorecore::types::*;
+
+use crate::app::message_handling::GuiMessage;
+
+use super::{MatrixViewMessage, MatrixViewState};
+
+const CELL_WIDTH: f32 = 120.0;
+/// How many parents and children are shown at most, since every cell is a widget of its own.
+const MAX_AXIS_LENGTH: usize = 50;
+
+pub(crate) fn new(state: &MatrixViewState) -> Element<'_, GuiMessage> {
+    let mut col = Column::new().push(filters(state));
+    if state.edit_mode {
+        col = col.push(cell_editor(state));
+    }
+    if state.parents.len() > MAX_AXIS_LENGTH || state.children.len() > MAX_AXIS_LENGTH {
+        col = col.push(
+            Text::new(format!(
+                "Showing at most {} of {} parents and {} children. Choose categories to narrow them down.",
+                MAX_AXIS_LENGTH,
+                state.parents.len(),
+                state.children.len()
+            ))
+            .width(Length::Fill),
+        );
+    }
+    col.push(
+        Scrollable::new(matrix(state))
+            .direction(Direction::Both {
+                vertical: Scrollbar::default(),
+                horizontal: Scrollbar::default(),
+            })
+            .width(Length::Fill)
+            .height(Length::Fill),
+    )
+    .into()
+}
+
+fn filters(state: &MatrixViewState) -> Row<'_, GuiMessage> {
+    let row_filter = PickList::new(
+        state.category_options.clone(),
+        Some(state.row_filter.clone()),
+        |f| GuiMessage::MatrixViewUpd(MatrixViewMessage::RowFilterSelected(f)),
+    );
+    let column_filter = PickList::new(
+        state.category_options.clone(),
+        Some(state.column_filter.clone()),
+        |f| GuiMessage::MatrixViewUpd(MatrixViewMessage::ColumnFilterSelected(f)),
+    );
+    let export =
+        button("Export CSV").on_press(GuiMessage::MatrixViewUpd(MatrixViewMessage::ExportCsv));
+    Row::new()
+        .push(Text::new("Parents:"))
+        .push(row_filter)
+        .push(Text::new("Children:"))
+        .push(column_filter)
+        .push(export)
+        .align_y(Alignment::Center)
+        .spacing(5)
+        .padding(5)
+}
+
+fn cell_editor(state: &MatrixViewState) -> Row<'_, GuiMessage> {
+    let (parent, child) = match &state.selected_cell {
+        Some(cell) => cell,
+        None => {
+            return Row::new()
+                .push(Text::new("Select a cell to edit its roles."))
+                .padding(5)
+        }
+    };
+    let input = TextInput::new("Comma-separated roles", &state.cell_text)
+        .on_input(|t| GuiMessage::MatrixViewUpd(MatrixViewMessage::CellTextUpdate(t)))
+        .on_submit(GuiMessage::MatrixViewUpd(MatrixViewMessage::CellSave));
+    let save = button("Save").on_press(GuiMessage::MatrixViewUpd(MatrixViewMessage::CellSave));
+    Row::new()
+        .push(Text::new(format!("{} -> {}:", parent, child)))
+        .push(input)
+        .push(save)
+        .align_y(Alignment::Center)
+        .spacing(5)
+        .padding(5)
+}
+
+fn matrix(state: &MatrixViewState) -> Column<'_, GuiMessage> {
+    let mut header = Row::new().push(cell_text(""));
+    let children = &state.children[..state.children.len().min(MAX_AXIS_LENGTH)];
+    for child in children {
+        header = header.push(cell_text(child.to_str()));
+    }
+    let mut col = Column::new().push(header.spacing(2));
+    for parent in state.parents.iter().take(MAX_AXIS_LENGTH) {
+        let mut row = Row::new().push(cell_text(parent.to_str()));
+        for child in children {
+            let roles = state.get_cell_text(parent.to_str(), child.to_str());
+            let parent: Parent = parent.to_str().into();
+            let child: Child = child.to_str().into();
+            let is_selected =
+                state.selected_cell.as_ref() == Some(&(parent.clone(), child.clone()));
+            let mut cell = button(Text::new(roles)).width(Length::Fixed(CELL_WIDTH));
+            cell = if is_selected {
+                cell.style(button::primary)
+            } else {
+                cell.style(button::secondary)
+            };
+            if state.edit_mode {
+                cell = cell.on_press(GuiMessage::MatrixViewUpd(MatrixViewMessage::CellSelected(
+                    parent, child,
+                )));
+            }
+            row = row.push(cell);
+        }
+        col = col.push(row.spacing(2));
+    }
+    col.spacing(2).padding(5)
+}
+
+fn cell_text(content: &str) -> Text<'_> {
+    Text::new(content).width(Length::Fixed(CELL_WIDTH))
+}
@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+
+use lorecore::types::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Ancestors,
+    Descendants,
+}
+
+/// An entry of an ancestor or descendant tree, listed depth-first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AncestryNode {
+    pub(crate) label: Label,
+    pub(crate) role: Role,
+    pub(crate) depth: usize,
+    /// The label already appears further up this branch, so the branch is not followed any further.
+    pub(crate) closes_cycle: bool,
+    /// The label was already reached via another branch, whose entry lists its relatives.
+    pub(crate) listed_before: bool,
+}
+
+/// Every label is expanded only once, so that intermarriage does not multiply the branches.
+pub(crate) fn build_tree(
+    relationships: &[EntityRelationship],
+    root: &Label,
+    roles: &[Role],
+    direction: Direction,
+) -> Vec<AncestryNode> {
+    let mut next: HashMap<&str, Vec<(&str, &Role)>> = HashMap::new();
+    for rel in relationships
+        .iter()
+        .filter(|rel| roles.is_empty() || roles.contains(&rel.role))
+    {
+        let (from, to) = match direction {
+            Direction::Ancestors => (rel.child.to_str(), rel.parent.to_str()),
+            Direction::Descendants => (rel.parent.to_str(), rel.child.to_str()),
+        };
+        next.entry(from).or_default().push((to, &rel.role));
+    }
+    for targets in next.values_mut() {
+        targets.sort_by(|a, b| a.0.cmp(b.0));
+    }
+
+    let mut nodes = vec![];
+    let mut path = vec![root.to_str()];
+    let mut visited = HashSet::from([root.to_str()]);
+    visit(&next, &mut path, &mut visited, &mut nodes);
+    nodes
+}
+
+fn visit<'a>(
+    next: &HashMap<&'a str, Vec<(&'a str, &'a Role)>>,
+    path: &mut Vec<&'a str>,
+    visited: &mut HashSet<&'a str>,
+    nodes: &mut Vec<AncestryNode>,
+) {
+    let current = match path.last() {
+        Some(current) => *current,
+        None => return,
+    };
+    for &(label, role) in next.get(current).into_iter().flatten() {
+        let closes_cycle = path.contains(&label);
+        let listed_before = !closes_cycle && !visited.insert(label);
+        nodes.push(AncestryNode {
+            label: label.into(),
+            role: role.clone(),
+            depth: path.len(),
+            closes_cycle,
+            listed_before,
+        });
+        if !closes_cycle && !listed_before {
+            path.push(label);
+            visit(next, path, visited, nodes);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel(parent: &str, child: &str, role: &str) -> EntityRelationship {
+        EntityRelationship {
+            parent: parent.into(),
+            child: child.into(),
+            role: role.into(),
+        }
+    }
+
+    #[test]
+    fn descendants_are_listed_with_depth_and_cycles_are_detected() {
+        let relationships = vec![
+            rel("queen", "prince", "mother"),
+            rel("prince", "heir", "father"),
+            rel("heir", "queen", "mentor"),
+            rel("queen", "knight", "liege"),
+        ];
+        let roles = vec!["mother".into(), "father".into(), "mentor".into()];
+        let tree = build_tree(
+            &relationships,
+            &"queen".into(),
+            &roles,
+            Direction::Descendants,
+        );
+        let summary: Vec<(&str, usize, bool)> = tree
+            .iter()
+            .map(|node| (node.label.to_str(), node.depth, node.closes_cycle))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("prince", 1, false), ("heir", 2, false), ("queen", 3, true)]
+        );
+
+        let tree = build_tree(&relationships, &"heir".into(), &[], Direction::Ancestors);
+        assert_eq!(tree[0].label, "prince".into());
+        assert_eq!(tree[1].label, "queen".into());
+        assert_eq!(tree[2].label, "heir".into());
+        assert!(tree[2].closes_cycle);
+    }
+
+    #[test]
+    fn labels_reached_twice_are_expanded_once() {
+        let relationships = vec![
+            rel("king", "elder", "father"),
+            rel("king", "younger", "father"),
+            rel("elder", "heir", "father"),
+            rel("younger", "heir", "mother"),
+            rel("heir", "grandchild", "father"),
+        ];
+        let tree = build_tree(&relationships, &"king".into(), &[], Direction::Descendants);
+        let summary: Vec<(&str, usize, bool)> = tree
+            .iter()
+            .map(|node| (node.label.to_str(), node.depth, node.listed_before))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("elder", 1, false),
+                ("heir", 2, false),
+                ("grandchild", 3, false),
+                ("younger", 1, false),
+                ("heir", 2, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use lorecore::{
+    extractions::extract_labels,
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, RelationshipSearchParams},
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+/// A relationship whose parent or child is not the label of any entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DanglingRelationship {
+    pub(crate) relationship: EntityRelationship,
+    pub(crate) missing_labels: Vec<Label>,
+}
+
+pub(crate) fn read_existing_labels(db: &LoreDatabase) -> Result<Vec<Label>, LoreGuiError> {
+    let entity_columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+    Ok(extract_labels(&entity_columns))
+}
+
+pub(crate) fn find_dangling_relationships(
+    db: &LoreDatabase,
+) -> Result<Vec<DanglingRelationship>, LoreGuiError> {
+    let labels = read_existing_labels(db)?;
+    let labels: HashSet<&str> = labels.iter().map(|label| label.to_str()).collect();
+    let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+    let dangling = relationships
+        .into_iter()
+        .filter_map(|relationship| {
+            let missing_labels: Vec<Label> =
+                [relationship.parent.to_str(), relationship.child.to_str()]
+                    .into_iter()
+                    .filter(|end| !labels.contains(end))
+                    .map(Label::from)
+                    .collect();
+            if missing_labels.is_empty() {
+                None
+            } else {
+                Some(DanglingRelationship {
+                    relationship,
+                    missing_labels,
+                })
+            }
+        })
+        .collect();
+    Ok(dangling)
+}
@@ -0,0 +1,115 @@
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, RelationshipSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+const INVERSE_ROLES_LABEL: &str = "_inverse_roles";
+
+/// Two roles that describe the same relationship from opposite ends, e.g. "mentor" and "apprentice".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InverseRolePair {
+    pub(crate) role: Role,
+    pub(crate) inverse: Role,
+}
+
+impl InverseRolePair {
+    pub(crate) fn to_column(&self) -> EntityColumn {
+        EntityColumn {
+            label: INVERSE_ROLES_LABEL.into(),
+            descriptor: self.role.to_str().into(),
+            description: self.inverse.to_str().into(),
+        }
+    }
+
+    fn from_column(col: &EntityColumn) -> Self {
+        InverseRolePair {
+            role: col.descriptor.to_str().into(),
+            inverse: col.description.to_str().into(),
+        }
+    }
+
+    pub(crate) fn involves(&self, role: &Role) -> bool {
+        &self.role == role || &self.inverse == role
+    }
+}
+
+pub(crate) fn inverse_role_pair_key(role: &Role) -> (Label, Descriptor) {
+    (INVERSE_ROLES_LABEL.into(), role.to_str().into())
+}
+
+pub(crate) fn read_inverse_role_pairs(
+    db: &LoreDatabase,
+) -> Result<Vec<InverseRolePair>, LoreGuiError> {
+    let search_params =
+        EntityColumnSearchParams::new(Some(SqlSearchText::exact(INVERSE_ROLES_LABEL)), None);
+    let pairs = db
+        .read_entity_columns(search_params)?
+        .iter()
+        .map(InverseRolePair::from_column)
+        .collect();
+    Ok(pairs)
+}
+
+pub(crate) fn inverse_of(pairs: &[InverseRolePair], role: &Role) -> Option<Role> {
+    pairs.iter().find_map(|pair| {
+        if &pair.role == role {
+            Some(pair.inverse.clone())
+        } else if &pair.inverse == role {
+            Some(pair.role.clone())
+        } else {
+            None
+        }
+    })
+}
+
+/// The relationship pointing back from the child to the parent with the inverse role, if there is one.
+pub(crate) fn mirror(
+    pairs: &[InverseRolePair],
+    rel: &EntityRelationship,
+) -> Option<EntityRelationship> {
+    let role = inverse_of(pairs, &rel.role)?;
+    Some(EntityRelationship {
+        parent: rel.child.to_str().into(),
+        child: rel.parent.to_str().into(),
+        role,
+    })
+}
+
+pub(crate) fn relationship_exists(
+    db: &LoreDatabase,
+    rel: &EntityRelationship,
+) -> Result<bool, LoreGuiError> {
+    let search_params = RelationshipSearchParams::new(
+        Some(SqlSearchText::exact(rel.parent.to_str())),
+        Some(SqlSearchText::exact(rel.child.to_str())),
+    );
+    let relationships = db.read_relationships(search_params)?;
+    Ok(relationships.iter().any(|r| r.role == rel.role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_is_found_in_both_directions() {
+        let pairs = vec![InverseRolePair {
+            role: "mentor".into(),
+            inverse: "apprentice".into(),
+        }];
+        assert_eq!(
+            inverse_of(&pairs, &"mentor".into()),
+            Some("apprentice".into())
+        );
+        assert_eq!(
+            inverse_of(&pairs, &"apprentice".into()),
+            Some("mentor".into())
+        );
+        assert_eq!(inverse_of(&pairs, &"friend".into()), None);
+    }
+}
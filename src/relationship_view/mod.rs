@@ -1,3 +1,4 @@
+use iced::widget::combo_box;
 use lorecore::{
     sql::{
         lore_database::LoreDatabase,
@@ -13,6 +14,16 @@ use crate::{
     errors::LoreGuiError,
 };
 
+use self::{
+    ancestry::AncestryNode, integrity::DanglingRelationship, inverse_roles::InverseRolePair,
+    roles::RoleUsage,
+};
+
+pub(crate) mod ancestry;
+pub(crate) mod integrity;
+pub(crate) mod inverse_roles;
+pub(crate) mod path;
+pub(crate) mod roles;
 pub(crate) mod widget;
 
 pub(super) struct RelationshipViewState {
@@ -21,6 +32,19 @@ pub(super) struct RelationshipViewState {
     pub(super) parent_view_state: DbColViewState<Parent>,
     pub(super) child_view_state: DbColViewState<Child>,
     pub(super) role_view_state: DbColViewState<Role>,
+    pub(super) inverse_role_pairs: Vec<InverseRolePair>,
+    pub(super) dangling_relationships: Vec<DanglingRelationship>,
+    pub(super) role_usages: Vec<RoleUsage>,
+    pub(super) ancestry_label: Option<Label>,
+    pub(super) followed_roles: Vec<Role>,
+    pub(super) ancestry_picker: combo_box::State<Label>,
+    pub(super) ancestors: Vec<AncestryNode>,
+    pub(super) descendants: Vec<AncestryNode>,
+    pub(super) path_source: Option<Label>,
+    pub(super) path_source_picker: combo_box::State<Label>,
+    pub(super) path_target: Option<Label>,
+    pub(super) path_target_picker: combo_box::State<Label>,
+    pub(super) paths: Vec<Vec<EntityRelationship>>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +52,16 @@ pub(super) enum RelationshipViewMessage {
     NewRelationship,
     ChangeRole(ChangeRoleData),
     DeleteRelationship(EntityRelationship),
+    RetargetRelationship(EntityRelationship),
+    NewInverseRolePair,
+    DeleteInverseRolePair(Role),
+    RenameRole(Role),
+    DeleteRole(Role),
+    AncestryLabelSelected(Label),
+    FollowedRoleToggled(Role, bool),
+    PathSourceSelected(Label),
+    PathTargetSelected(Label),
+    PathStepSelected(EntityRelationship),
     ParentViewUpdate(ColViewMes<Parent>),
     ChildViewUpdate(ColViewMes<Child>),
     RoleViewUpdate(ColViewMes<Role>),
@@ -104,6 +138,33 @@ impl RelationshipViewState {
         Ok(roles)
     }
 
+    /// Labels that take part in at least one relationship.
+    pub(super) fn get_ancestry_options(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<Label>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+        let mut labels: Vec<Label> = extract_parents(&relationships)
+            .iter()
+            .map(|p| p.to_str().into())
+            .chain(
+                extract_children(&relationships)
+                    .iter()
+                    .map(|c| c.to_str().into()),
+            )
+            .collect();
+        labels.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+        labels.dedup();
+        if !self.display_protected {
+            labels.retain(|label| !label.is_protected());
+        }
+        Ok(labels)
+    }
+
     pub(super) fn get_selected_parent(&self) -> Option<Parent> {
         self.parent_view_state.get_selected().0.clone()
     }
@@ -137,6 +198,19 @@ impl Default for RelationshipViewState {
             parent_view_state: DbColViewState::new(vec![], true),
             child_view_state: DbColViewState::new(vec![], true),
             role_view_state: DbColViewState::default(),
+            inverse_role_pairs: vec![],
+            dangling_relationships: vec![],
+            role_usages: vec![],
+            ancestry_label: None,
+            followed_roles: vec![],
+            ancestry_picker: combo_box::State::new(vec![]),
+            ancestors: vec![],
+            descendants: vec![],
+            path_source: None,
+            path_source_picker: combo_box::State::new(vec![]),
+            path_target: None,
+            path_target_picker: combo_box::State::new(vec![]),
+            paths: vec![],
         }
     }
 }
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, VecDeque};
+
+use lorecore::types::*;
+
+/// How many shortest paths are shown at most.
+pub(crate) const MAX_PATHS: usize = 10;
+
+/// Relationships are followed in either direction, and restricted to `roles` unless it is empty.
+pub(crate) fn shortest_paths(
+    relationships: &[EntityRelationship],
+    from: &Label,
+    to: &Label,
+    roles: &[Role],
+    limit: usize,
+) -> Vec<Vec<EntityRelationship>> {
+    let mut neighbours: HashMap<&str, Vec<(&str, &EntityRelationship)>> = HashMap::new();
+    for rel in relationships
+        .iter()
+        .filter(|rel| roles.is_empty() || roles.contains(&rel.role))
+    {
+        let (parent, child) = (rel.parent.to_str(), rel.child.to_str());
+        neighbours.entry(parent).or_default().push((child, rel));
+        neighbours.entry(child).or_default().push((parent, rel));
+    }
+
+    let (from, to) = (from.to_str(), to.to_str());
+    let mut distances = HashMap::from([(from, 0)]);
+    let mut reached_via: HashMap<&str, Vec<(&str, &EntityRelationship)>> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[current];
+        if distances.get(to).is_some_and(|d| *d <= distance) {
+            break;
+        }
+        for &(neighbour, rel) in neighbours.get(current).into_iter().flatten() {
+            match distances.get(neighbour) {
+                None => {
+                    distances.insert(neighbour, distance + 1);
+                    queue.push_back(neighbour);
+                }
+                Some(d) if *d == distance + 1 => (),
+                Some(_) => continue,
+            }
+            reached_via
+                .entry(neighbour)
+                .or_default()
+                .push((current, rel));
+        }
+    }
+
+    let mut paths = vec![];
+    if distances.contains_key(to) {
+        collect_paths(&reached_via, to, from, &mut vec![], &mut paths, limit);
+    }
+    paths
+}
+
+/// Walks back from `current` to `from`, completing the path whose steps after `current` are given.
+fn collect_paths<'a>(
+    reached_via: &HashMap<&'a str, Vec<(&'a str, &'a EntityRelationship)>>,
+    current: &'a str,
+    from: &str,
+    steps: &mut Vec<&'a EntityRelationship>,
+    paths: &mut Vec<Vec<EntityRelationship>>,
+    limit: usize,
+) {
+    if paths.len() >= limit {
+        return;
+    }
+    if current == from {
+        paths.push(steps.iter().rev().map(|rel| (*rel).clone()).collect());
+        return;
+    }
+    for &(previous, rel) in reached_via.get(current).into_iter().flatten() {
+        steps.push(rel);
+        collect_paths(reached_via, previous, from, steps, paths, limit);
+        steps.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel(parent: &str, child: &str, role: &str) -> EntityRelationship {
+        EntityRelationship {
+            parent: parent.into(),
+            child: child.into(),
+            role: role.into(),
+        }
+    }
+
+    fn roles_of(paths: &[Vec<EntityRelationship>]) -> Vec<Vec<&str>> {
+        paths
+            .iter()
+            .map(|path| path.iter().map(|rel| rel.role.to_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn path_follows_relationships_in_both_directions() {
+        let relationships = vec![
+            rel("king", "duke", "liege"),
+            rel("king", "queen", "spouse"),
+            rel("baron", "duke", "vassal"),
+            rel("queen", "baron", "patron"),
+        ];
+        let (duke, queen) = ("duke".into(), "queen".into());
+        let paths = shortest_paths(&relationships, &duke, &queen, &[], MAX_PATHS);
+        assert_eq!(
+            roles_of(&paths),
+            vec![vec!["liege", "spouse"], vec!["vassal", "patron"]]
+        );
+
+        let roles = vec!["vassal".into(), "patron".into()];
+        let paths = shortest_paths(&relationships, &duke, &queen, &roles, MAX_PATHS);
+        assert_eq!(roles_of(&paths), vec![vec!["vassal", "patron"]]);
+
+        let roles = vec!["liege".into()];
+        assert!(shortest_paths(&relationships, &duke, &queen, &roles, MAX_PATHS).is_empty());
+    }
+
+    #[test]
+    fn only_the_requested_number_of_paths_is_returned() {
+        let relationships = vec![
+            rel("a", "b1", "r"),
+            rel("a", "b2", "r"),
+            rel("b1", "c", "r"),
+            rel("b2", "c", "r"),
+            rel("c", "d1", "r"),
+            rel("c", "d2", "r"),
+            rel("d1", "e", "r"),
+            rel("d2", "e", "r"),
+        ];
+        let (a, e) = ("a".into(), "e".into());
+        assert_eq!(shortest_paths(&relationships, &a, &e, &[], 10).len(), 4);
+        assert_eq!(shortest_paths(&relationships, &a, &e, &[], 3).len(), 3);
+    }
+}
@@ -0,0 +1,48 @@
+use lorecore::{
+    sql::{lore_database::LoreDatabase, search_params::RelationshipSearchParams},
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+use super::inverse_roles::read_inverse_role_pairs;
+
+/// A role of the vocabulary and the number of relationships using it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RoleUsage {
+    pub(crate) role: Role,
+    pub(crate) count: usize,
+}
+
+/// Roles only mentioned in the inverse role table are part of the vocabulary with a count of zero.
+pub(crate) fn read_role_usages(db: &LoreDatabase) -> Result<Vec<RoleUsage>, LoreGuiError> {
+    let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+    let mut usages: Vec<RoleUsage> = vec![];
+    let mut add = |role: &Role, count: usize| match usages.iter_mut().find(|u| &u.role == role) {
+        Some(usage) => usage.count += count,
+        None => usages.push(RoleUsage {
+            role: role.clone(),
+            count,
+        }),
+    };
+    for rel in relationships.iter() {
+        add(&rel.role, 1);
+    }
+    for pair in read_inverse_role_pairs(db)? {
+        add(&pair.role, 0);
+        add(&pair.inverse, 0);
+    }
+    usages.sort_by(|a, b| a.role.to_str().cmp(b.role.to_str()));
+    Ok(usages)
+}
+
+pub(crate) fn read_relationships_with_role(
+    db: &LoreDatabase,
+    role: &Role,
+) -> Result<Vec<EntityRelationship>, LoreGuiError> {
+    let relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+    Ok(relationships
+        .into_iter()
+        .filter(|rel| &rel.role == role)
+        .collect())
+}
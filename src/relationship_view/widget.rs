@@ -1,21 +1,37 @@
 use iced::widget::button;
 use iced::Alignment;
 use iced::{
-    widget::{Column, Row},
+    widget::{combo_box, Checkbox, Column, Row, Scrollable, Text},
     Element, Length,
 };
 use lorecore::types::*;
 
-use crate::{app::message_handling::GuiMessage, db_col_view, dialog::change_role::ChangeRoleData};
+use crate::{
+    app::message_handling::GuiMessage,
+    db_col_view,
+    dialog::{change_role::ChangeRoleData, new_entity::NewEntityData},
+};
 
-use super::{RelationshipViewMessage, RelationshipViewState};
+use super::{
+    ancestry::AncestryNode, path::MAX_PATHS, RelationshipViewMessage, RelationshipViewState,
+};
 
 pub(crate) fn new(state: &RelationshipViewState) -> Element<'_, GuiMessage> {
     let mut col = Column::new();
+    let mut side_col = Column::new().push(ancestry(state)).push(path(state));
     if state.edit_mode {
-        col = col.push(buttons(state))
+        col = col.push(buttons(state));
+        side_col = side_col.push(roles(state)).push(inverse_roles(state));
     }
-    col.push(col_views(state)).into()
+    if !state.dangling_relationships.is_empty() {
+        side_col = side_col.push(dangling_relationships(state));
+    }
+    col.push(
+        Row::new()
+            .push(col_views(state))
+            .push(Scrollable::new(side_col)),
+    )
+    .into()
 }
 
 fn buttons(state: &RelationshipViewState) -> Row<'_, GuiMessage> {
@@ -39,14 +55,208 @@ fn buttons(state: &RelationshipViewState) -> Row<'_, GuiMessage> {
             RelationshipViewMessage::DeleteRelationship(relationship),
         ));
     }
+    let new_inverse_role_pair = button("New Inverse Role Pair").on_press(
+        GuiMessage::RelationshipViewUpd(RelationshipViewMessage::NewInverseRolePair),
+    );
     Row::new()
         .push(new_relationship)
         .push(change_role)
         .push(delete_relationship)
+        .push(new_inverse_role_pair)
+        .spacing(5)
+        .padding(5)
+}
+
+fn ancestry(state: &RelationshipViewState) -> Column<'_, GuiMessage> {
+    let picker = combo_box(
+        &state.ancestry_picker,
+        "Type a label...",
+        state.ancestry_label.as_ref(),
+        |label| {
+            GuiMessage::RelationshipViewUpd(RelationshipViewMessage::AncestryLabelSelected(label))
+        },
+    );
+    let mut col = Column::new()
+        .push(Text::new("Ancestry"))
+        .push(picker)
+        .push(Text::new("Follow roles (all if none are checked):"));
+    for usage in state.role_usages.iter().filter(|usage| usage.count > 0) {
+        let role = usage.role.clone();
+        let checked = state.followed_roles.contains(&role);
+        col = col.push(
+            Checkbox::new(checked)
+                .label(role.to_str().to_string())
+                .on_toggle(move |checked| {
+                    GuiMessage::RelationshipViewUpd(RelationshipViewMessage::FollowedRoleToggled(
+                        role.clone(),
+                        checked,
+                    ))
+                }),
+        );
+    }
+    if state.ancestry_label.is_none() {
+        return col.spacing(5).padding(5);
+    }
+    let has_cycle = state
+        .ancestors
+        .iter()
+        .chain(state.descendants.iter())
+        .any(|node| node.closes_cycle);
+    if has_cycle {
+        col = col.push(Text::new("These relationships contain a cycle."));
+    }
+    col.push(Text::new("Ancestors:"))
+        .push(ancestry_tree(&state.ancestors))
+        .push(Text::new("Descendants:"))
+        .push(ancestry_tree(&state.descendants))
         .spacing(5)
         .padding(5)
 }
 
+fn path(state: &RelationshipViewState) -> Column<'_, GuiMessage> {
+    let source_picker = combo_box(
+        &state.path_source_picker,
+        "Type a label...",
+        state.path_source.as_ref(),
+        |label| GuiMessage::RelationshipViewUpd(RelationshipViewMessage::PathSourceSelected(label)),
+    );
+    let target_picker = combo_box(
+        &state.path_target_picker,
+        "Type a label...",
+        state.path_target.as_ref(),
+        |label| GuiMessage::RelationshipViewUpd(RelationshipViewMessage::PathTargetSelected(label)),
+    );
+    let mut col = Column::new()
+        .push(Text::new("Shortest paths from:"))
+        .push(source_picker)
+        .push(Text::new("to:"))
+        .push(target_picker);
+    match (&state.path_source, &state.path_target) {
+        (Some(from), Some(to)) if from == to => {
+            col = col.push(Text::new("Both ends of the path are the same label."))
+        }
+        (Some(_), Some(_)) if state.paths.is_empty() => col = col.push(Text::new("No path found.")),
+        (Some(_), Some(_)) => {
+            for (i, steps) in state.paths.iter().take(MAX_PATHS).enumerate() {
+                col = col.push(Text::new(format!("Path {}:", i + 1)));
+                for rel in steps {
+                    let step = button(Text::new(format!(
+                        "{} -> {} ({})",
+                        rel.parent, rel.child, rel.role
+                    )))
+                    .on_press(GuiMessage::RelationshipViewUpd(
+                        RelationshipViewMessage::PathStepSelected(rel.clone()),
+                    ));
+                    col = col.push(step);
+                }
+            }
+            if state.paths.len() > MAX_PATHS {
+                col = col.push(Text::new(format!(
+                    "Only the first {} shortest paths are shown.",
+                    MAX_PATHS
+                )));
+            }
+        }
+        _ => (),
+    }
+    col.spacing(5).padding(5)
+}
+
+fn ancestry_tree(nodes: &[AncestryNode]) -> Column<'_, GuiMessage> {
+    let mut col = Column::new();
+    for node in nodes {
+        let mut text = format!(
+            "{}{} ({}, depth {})",
+            "    ".repeat(node.depth - 1),
+            node.label,
+            node.role,
+            node.depth
+        );
+        if node.closes_cycle {
+            text = format!("{} - cycle", text);
+        } else if node.listed_before {
+            text = format!("{} - see above", text);
+        }
+        col = col.push(Text::new(text));
+    }
+    col
+}
+
+fn roles(state: &RelationshipViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(Text::new("Roles"));
+    for usage in state.role_usages.iter() {
+        let rename = button("Rename").on_press(GuiMessage::RelationshipViewUpd(
+            RelationshipViewMessage::RenameRole(usage.role.clone()),
+        ));
+        let mut delete = button("Delete");
+        if usage.count == 0 {
+            delete = delete.on_press(GuiMessage::RelationshipViewUpd(
+                RelationshipViewMessage::DeleteRole(usage.role.clone()),
+            ));
+        }
+        let row = Row::new()
+            .push(Text::new(format!("{} ({})", usage.role, usage.count)))
+            .push(rename)
+            .push(delete)
+            .align_y(Alignment::Center)
+            .spacing(5);
+        col = col.push(row);
+    }
+    col.spacing(5).padding(5)
+}
+
+fn inverse_roles(state: &RelationshipViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(Text::new("Inverse Roles"));
+    for pair in state.inverse_role_pairs.iter() {
+        let delete = button("Delete").on_press(GuiMessage::RelationshipViewUpd(
+            RelationshipViewMessage::DeleteInverseRolePair(pair.role.clone()),
+        ));
+        let row = Row::new()
+            .push(Text::new(format!("{} / {}", pair.role, pair.inverse)))
+            .push(delete)
+            .align_y(Alignment::Center)
+            .spacing(5);
+        col = col.push(row);
+    }
+    col.spacing(5).padding(5)
+}
+
+fn dangling_relationships(state: &RelationshipViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(Text::new("Dangling Relationships"));
+    for dangling in state.dangling_relationships.iter() {
+        let rel = &dangling.relationship;
+        let missing = dangling
+            .missing_labels
+            .iter()
+            .map(|label| label.to_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        col = col.push(Text::new(format!(
+            "{} -> {} ({}), missing: {}",
+            rel.parent, rel.child, rel.role, missing
+        )));
+        if !state.edit_mode {
+            continue;
+        }
+        let mut fixes = Row::new()
+            .push(button("Delete").on_press(GuiMessage::RelationshipViewUpd(
+                RelationshipViewMessage::DeleteRelationship(rel.clone()),
+            )))
+            .push(button("Retarget").on_press(GuiMessage::RelationshipViewUpd(
+                RelationshipViewMessage::RetargetRelationship(rel.clone()),
+            )));
+        for label in dangling.missing_labels.iter() {
+            let stub = NewEntityData::stub(label.clone());
+            fixes = fixes.push(
+                button(Text::new(format!("Create Stub {}", label)))
+                    .on_press(GuiMessage::NewEntity(stub)),
+            );
+        }
+        col = col.push(fixes.spacing(5));
+    }
+    col.spacing(5).padding(5)
+}
+
 fn col_views(state: &RelationshipViewState) -> Row<'_, GuiMessage> {
     Row::new()
         .push(db_col_view::widget::new(
@@ -0,0 +1,272 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    ops::Range,
+};
+
+use lorecore::{
+    extractions::extract_labels,
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, HistoryItemSearchParams},
+    },
+    types::*,
+};
+
+use crate::{
+    dialog::{find_replace::read_text_sources, label_picker::read_label_options},
+    entity_view::category::CATEGORY_DESCRIPTOR,
+    errors::LoreGuiError,
+    history_view::{dating::Dating, properties::get_dating},
+};
+
+use super::unknown_terms::find_unknown_terms;
+
+const NAME_DESCRIPTOR: &str = "_name";
+/// Labels at most this many edits apart are reported as nearly identical.
+const MAX_LABEL_DISTANCE: usize = 2;
+/// Short labels are only reported if they differ by a single edit.
+const CHARS_PER_EDIT: usize = 5;
+
+#[derive(Debug, Clone)]
+pub(crate) enum QualityIssue {
+    MissingName(Label),
+    MissingCategory(Label),
+    OnlyProtectedDescriptors(Label),
+    EmptyDescription(Label, Descriptor),
+    EmptyHistoryContent(Year, Day, Timestamp),
+    MissingDay(Year, Day, Timestamp),
+    SimilarLabels(Label, Label),
+    UnknownTerm(String, usize),
+}
+
+impl QualityIssue {
+    pub(crate) fn section(&self) -> &'static str {
+        match self {
+            QualityIssue::MissingName(_) => "Entities without a name",
+            QualityIssue::MissingCategory(_) => "Entities without a category",
+            QualityIssue::OnlyProtectedDescriptors(_) => "Entities with only protected descriptors",
+            QualityIssue::EmptyDescription(_, _) => "Empty descriptions",
+            QualityIssue::EmptyHistoryContent(_, _, _) => "History items without content",
+            QualityIssue::MissingDay(_, _, _) => "History items without a day",
+            QualityIssue::SimilarLabels(_, _) => "Nearly identical labels",
+            QualityIssue::UnknownTerm(_, _) => "Frequently mentioned terms without an entity",
+        }
+    }
+}
+
+impl Display for QualityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QualityIssue::MissingName(label)
+            | QualityIssue::MissingCategory(label)
+            | QualityIssue::OnlyProtectedDescriptors(label) => write!(f, "{}", label),
+            QualityIssue::EmptyDescription(label, descriptor) => {
+                write!(f, "{}: {}", label, descriptor)
+            }
+            QualityIssue::EmptyHistoryContent(year, day, _)
+            | QualityIssue::MissingDay(year, day, _) => write!(f, "Year {}, day {}", year, day),
+            QualityIssue::SimilarLabels(a, b) => write!(f, "{} and {}", a, b),
+            QualityIssue::UnknownTerm(term, count) => write!(f, "{} ({} mentions)", term, count),
+        }
+    }
+}
+
+/// Scans the database for incomplete or suspicious lore, grouped by section.
+pub(crate) fn find_quality_issues(db: &LoreDatabase) -> Result<Vec<QualityIssue>, LoreGuiError> {
+    let entity_columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+    let mut labels = extract_labels(&entity_columns);
+    labels.retain(|label| !label.is_protected());
+    labels.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+    let mut columns_by_label: HashMap<&str, Vec<&EntityColumn>> = HashMap::new();
+    for col in entity_columns.iter() {
+        columns_by_label
+            .entry(col.label.to_str())
+            .or_default()
+            .push(col);
+    }
+    let columns_of = |label: &Label| {
+        columns_by_label
+            .get(label.to_str())
+            .into_iter()
+            .flatten()
+            .copied()
+    };
+    let has_descriptor = |label: &Label, descriptor: &str| {
+        columns_of(label).any(|col| col.descriptor.to_str() == descriptor)
+    };
+
+    let mut issues = vec![];
+    for label in labels.iter() {
+        if !has_descriptor(label, NAME_DESCRIPTOR) {
+            issues.push(QualityIssue::MissingName(label.clone()));
+        }
+    }
+    for label in labels.iter() {
+        if !has_descriptor(label, CATEGORY_DESCRIPTOR) {
+            issues.push(QualityIssue::MissingCategory(label.clone()));
+        }
+    }
+    for label in labels.iter() {
+        if columns_of(label).all(|col| col.descriptor.is_protected()) {
+            issues.push(QualityIssue::OnlyProtectedDescriptors(label.clone()));
+        }
+    }
+    for label in labels.iter() {
+        for col in columns_of(label) {
+            if col.description.to_str().trim().is_empty() {
+                issues.push(QualityIssue::EmptyDescription(
+                    label.clone(),
+                    col.descriptor.clone(),
+                ));
+            }
+        }
+    }
+
+    let history_items =
+        db.read_history_items(HistoryItemSearchParams::new(None, None, None, None))?;
+    for item in history_items.iter() {
+        if item.content.to_str().trim().is_empty() {
+            issues.push(QualityIssue::EmptyHistoryContent(
+                item.year,
+                item.day,
+                item.timestamp,
+            ));
+        }
+    }
+    // Items dated by a range, a decade or a century are not expected to have a day.
+    for item in history_items.iter() {
+        if item.day == Day::NONE && get_dating(&item.properties) == Dating::default() {
+            issues.push(QualityIssue::MissingDay(
+                item.year,
+                item.day,
+                item.timestamp,
+            ));
+        }
+    }
+
+    issues.extend(
+        find_similar_labels(&labels)
+            .into_iter()
+            .map(|(a, b)| QualityIssue::SimilarLabels(a, b)),
+    );
+    issues.extend(
+        find_unknown_terms(&read_text_sources(db)?, &read_label_options(db)?)
+            .into_iter()
+            .map(|(term, count)| QualityIssue::UnknownTerm(term, count)),
+    );
+    Ok(issues)
+}
+
+/// Labels that only differ in their digits, such as numbered ones, are not reported.
+fn find_similar_labels(labels: &[Label]) -> Vec<(Label, Label)> {
+    let lowercase: Vec<Vec<char>> = labels
+        .iter()
+        .map(|label| label.to_str().to_lowercase().chars().collect())
+        .collect();
+    // Similar labels share an untouched segment, which spares comparing every pair of labels.
+    let mut segments: HashMap<(usize, usize, &[char]), Vec<usize>> = HashMap::new();
+    for (i, chars) in lowercase.iter().enumerate() {
+        if chars.len() < CHARS_PER_EDIT {
+            continue;
+        }
+        for (s, range) in segment_ranges(chars.len()).enumerate() {
+            let key = (chars.len(), s, &chars[range]);
+            segments.entry(key).or_default().push(i);
+        }
+    }
+    let mut candidates = BTreeSet::new();
+    for (j, chars) in lowercase.iter().enumerate() {
+        if chars.len() < CHARS_PER_EDIT {
+            continue;
+        }
+        let min_len = chars.len().saturating_sub(MAX_LABEL_DISTANCE);
+        for len in min_len..=chars.len() + MAX_LABEL_DISTANCE {
+            for (s, range) in segment_ranges(len).enumerate() {
+                let earliest = range.start.saturating_sub(MAX_LABEL_DISTANCE);
+                for start in earliest..=range.start + MAX_LABEL_DISTANCE {
+                    let end = start + range.len();
+                    if end > chars.len() {
+                        break;
+                    }
+                    let found = segments.get(&(len, s, &chars[start..end]));
+                    for &i in found.into_iter().flatten().filter(|&&i| i != j) {
+                        candidates.insert((i.min(j), i.max(j)));
+                    }
+                }
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|&(i, j)| is_similar(&lowercase[i], &lowercase[j]))
+        .map(|(i, j)| (labels[i].clone(), labels[j].clone()))
+        .collect()
+}
+
+/// Splits a label of the given length into one more segment than edits are allowed.
+fn segment_ranges(len: usize) -> impl Iterator<Item = Range<usize>> {
+    let count = MAX_LABEL_DISTANCE + 1;
+    (0..count).map(move |s| s * len / count..(s + 1) * len / count)
+}
+
+fn is_similar(a: &[char], b: &[char]) -> bool {
+    let max = (a.len().min(b.len()) / CHARS_PER_EDIT).min(MAX_LABEL_DISTANCE);
+    let without_digits = |chars: &[char]| -> Vec<char> {
+        chars
+            .iter()
+            .filter(|c| !c.is_ascii_digit())
+            .copied()
+            .collect()
+    };
+    max > 0 && without_digits(a) != without_digits(b) && edit_distance(a, b, max) <= max
+}
+
+/// The Levenshtein distance between two strings, or some value above `max` if it exceeds `max`.
+fn edit_distance(a: &[char], b: &[char], max: usize) -> usize {
+    if a.len().abs_diff(b.len()) > max {
+        return max + 1;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().all(|&d| d > max) {
+            return max + 1;
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting"), 5), 3);
+        assert_eq!(edit_distance(&chars("gandalf"), &chars("gandalf"), 2), 0);
+        assert_eq!(edit_distance(&chars("gandalf"), &chars("grandalf"), 2), 1);
+        assert!(edit_distance(&chars("frodo"), &chars("sam"), 2) > 2);
+    }
+
+    #[test]
+    fn only_genuine_near_duplicates_are_similar() {
+        let labels: Vec<Label> = [
+            "npc_01", "npc_02", "gandalf", "grandalf", "sam", "sim", "boromir", "boromri",
+        ]
+        .into_iter()
+        .map(Label::from)
+        .collect();
+        let pairs: Vec<(&str, &str)> = find_similar_labels(&labels)
+            .iter()
+            .map(|(a, b)| (a.to_str(), b.to_str()))
+            .collect();
+        assert_eq!(pairs, vec![("gandalf", "grandalf")]);
+    }
+}
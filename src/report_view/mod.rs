@@ -0,0 +1,26 @@
+use lorecore::sql::lore_database::LoreDatabase;
+
+use crate::errors::LoreGuiError;
+
+use self::issues::{find_quality_issues, QualityIssue};
+
+pub(crate) mod issues;
+pub(crate) mod unknown_terms;
+pub(crate) mod widget;
+
+#[derive(Debug, Default)]
+pub(super) struct ReportViewState {
+    pub(super) issues: Vec<QualityIssue>,
+}
+
+impl ReportViewState {
+    pub(super) fn get_current_issues(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<QualityIssue>, LoreGuiError> {
+        match db {
+            Some(db) => find_quality_issues(db),
+            None => Ok(vec![]),
+        }
+    }
+}
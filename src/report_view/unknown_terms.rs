@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use crate::dialog::{find_replace::TextSource, label_picker::LabelOption};
+
+/// Terms mentioned less often are not reported.
+const MIN_OCCURRENCES: usize = 2;
+/// How many of the most frequent terms are reported.
+const MAX_TERMS: usize = 20;
+
+/// The first word of a sentence is left out, since it is most likely capitalized for grammar.
+pub(crate) fn find_unknown_terms(
+    sources: &[TextSource],
+    label_options: &[LabelOption],
+) -> Vec<(String, usize)> {
+    let (term_regex, reference_regex) = match (
+        Regex::new(r"\b\p{Lu}[\p{L}'-]*(?:[ \t]+\p{Lu}[\p{L}'-]*)*"),
+        Regex::new(r"\\reference\{[^}]*\}"),
+    ) {
+        (Ok(term_regex), Ok(reference_regex)) => (term_regex, reference_regex),
+        _ => return vec![],
+    };
+    let known: HashSet<String> = label_options
+        .iter()
+        .flat_map(|option| {
+            let name = option
+                .name
+                .as_deref()
+                .map(|name| name.trim().to_lowercase());
+            std::iter::once(option.label.to_str().to_lowercase()).chain(name)
+        })
+        .collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for source in sources {
+        let text = reference_regex.replace_all(&source.text, " ");
+        for found in term_regex.find_iter(&text) {
+            let mut term = found.as_str();
+            if known.contains(&term.to_lowercase()) {
+                continue;
+            }
+            if is_sentence_start(&text, found.start()) {
+                term = match term.split_once(char::is_whitespace) {
+                    Some((_, rest)) => rest.trim_start(),
+                    None => continue,
+                };
+                if known.contains(&term.to_lowercase()) {
+                    continue;
+                }
+            }
+            match counts.get_mut(term) {
+                Some(n) => *n += 1,
+                None => {
+                    counts.insert(term.to_string(), 1);
+                }
+            }
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .filter(|(_, n)| *n >= MIN_OCCURRENCES)
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(MAX_TERMS);
+    counts
+}
+
+fn is_sentence_start(text: &str, start: usize) -> bool {
+    let before = text[..start].trim_end();
+    before.is_empty()
+        || before.ends_with(['.', '!', '?', ':'])
+        || text[before.len()..start].contains('\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialog::find_replace::TextLocation;
+
+    fn source(text: &str) -> TextSource {
+        TextSource {
+            location: TextLocation::Description("label".into(), "descriptor".into()),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn recurring_unknown_capitalized_terms_are_ranked() {
+        let label_options = vec![LabelOption {
+            label: "frodo".into(),
+            name: Some("Frodo Baggins\n".to_string()),
+        }];
+        let sources = vec![
+            source("Frodo Baggins left Minas Tirith for Mordor. Then he rested."),
+            source("In Minas Tirith the \\reference{frodo} met Gandalf."),
+            source("East of Minas Tirith lies Mordor. Then it got dark."),
+        ];
+        let terms = find_unknown_terms(&sources, &label_options);
+        assert_eq!(
+            terms,
+            vec![("Minas Tirith".to_string(), 3), ("Mordor".to_string(), 2)]
+        );
+    }
+}
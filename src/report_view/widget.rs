@@ -0,0 +1,65 @@
+use iced::{
+    widget::{button, Column, Row, Scrollable, Text},
+    Alignment, Element, Length,
+};
+
+use crate::{app::message_handling::GuiMessage, dialog::new_entity::NewEntityData};
+
+use super::{issues::QualityIssue, ReportViewState};
+
+pub(crate) fn new(state: &ReportViewState) -> Element<'_, GuiMessage> {
+    let refresh_button = button("Refresh").on_press(GuiMessage::RefreshReport);
+    let mut col = Column::new().push(refresh_button).spacing(5).padding(5);
+    if state.issues.is_empty() {
+        col = col.push(Text::new("No issues found."));
+    }
+    let mut current_section = None;
+    for issue in state.issues.iter() {
+        if current_section != Some(issue.section()) {
+            current_section = Some(issue.section());
+            let count = state
+                .issues
+                .iter()
+                .filter(|i| i.section() == issue.section())
+                .count();
+            col = col.push(Text::new(format!("{} ({})", issue.section(), count)).size(20));
+        }
+        col = col.push(issue_row(issue));
+    }
+    Scrollable::new(col)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+fn issue_row(issue: &QualityIssue) -> Row<'_, GuiMessage> {
+    let mut row = Row::new()
+        .push(Text::new(issue.to_string()))
+        .align_y(Alignment::Center)
+        .spacing(5);
+    match issue {
+        QualityIssue::MissingName(label)
+        | QualityIssue::MissingCategory(label)
+        | QualityIssue::OnlyProtectedDescriptors(label)
+        | QualityIssue::EmptyDescription(label, _) => {
+            row = row.push(button("Go to Entity").on_press(GuiMessage::GoToEntity(label.clone())))
+        }
+        QualityIssue::EmptyHistoryContent(year, day, timestamp)
+        | QualityIssue::MissingDay(year, day, timestamp) => {
+            row = row.push(
+                button("Go to History Item")
+                    .on_press(GuiMessage::GoToHistoryItem(*year, *day, *timestamp)),
+            )
+        }
+        QualityIssue::SimilarLabels(a, b) => {
+            row = row
+                .push(button(Text::new(a.to_str())).on_press(GuiMessage::GoToEntity(a.clone())))
+                .push(button(Text::new(b.to_str())).on_press(GuiMessage::GoToEntity(b.clone())))
+        }
+        QualityIssue::UnknownTerm(term, _) => {
+            let data = NewEntityData::from_term(term);
+            row = row.push(button("Create Entity").on_press(GuiMessage::OpenNewEntityDialog(data)))
+        }
+    }
+    row
+}
@@ -0,0 +1,125 @@
+use iced::{
+    mouse,
+    widget::canvas::{self, Frame, Geometry, Path, Stroke, Text},
+    Point, Rectangle, Renderer, Size, Theme,
+};
+
+use crate::app::message_handling::GuiMessage;
+
+const MARGIN: f32 = 10.0;
+const LABEL_WIDTH: f32 = 150.0;
+const BAR_HEIGHT: f32 = 20.0;
+const BAR_SPACING: f32 = 4.0;
+
+/// Horizontal bars, one per entry, scaled to the largest count.
+pub(super) struct BarChart {
+    pub(super) bars: Vec<(String, usize)>,
+}
+
+impl BarChart {
+    pub(super) fn height(&self) -> f32 {
+        2.0 * MARGIN + self.bars.len() as f32 * (BAR_HEIGHT + BAR_SPACING)
+    }
+}
+
+impl canvas::Program<GuiMessage> for BarChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+        let max = self.bars.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+        let usable_width = (bounds.width - 2.0 * MARGIN - 2.0 * LABEL_WIDTH).max(0.0);
+        for (i, (name, n)) in self.bars.iter().enumerate() {
+            let y = MARGIN + i as f32 * (BAR_HEIGHT + BAR_SPACING);
+            frame.fill_text(Text {
+                content: name.clone(),
+                position: Point::new(MARGIN, y + 2.0),
+                color: palette.text,
+                ..Text::default()
+            });
+            let width = *n as f32 / max as f32 * usable_width;
+            frame.fill_rectangle(
+                Point::new(MARGIN + LABEL_WIDTH, y),
+                Size::new(width.max(1.0), BAR_HEIGHT),
+                palette.primary,
+            );
+            frame.fill_text(Text {
+                content: n.to_string(),
+                position: Point::new(MARGIN + LABEL_WIDTH + width + 5.0, y + 2.0),
+                color: palette.text,
+                ..Text::default()
+            });
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+/// The cumulative number of history items over their creation timestamps.
+pub(super) struct GrowthChart<'a> {
+    pub(super) growth: &'a [(i64, usize)],
+}
+
+impl canvas::Program<GuiMessage> for GrowthChart<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+        let (first, last) = match (self.growth.first(), self.growth.last()) {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => return vec![frame.into_geometry()],
+        };
+        let total = self.growth.len() as f32;
+        let usable_width = bounds.width - 2.0 * MARGIN;
+        let usable_height = bounds.height - 2.0 * MARGIN;
+        let x_pos = |timestamp: i64| {
+            if last == first {
+                bounds.width / 2.0
+            } else {
+                MARGIN + (timestamp - first) as f32 / (last - first) as f32 * usable_width
+            }
+        };
+        let y_pos = |count: usize| bounds.height - MARGIN - count as f32 / total * usable_height;
+
+        let axes = Path::new(|builder| {
+            builder.move_to(Point::new(MARGIN, MARGIN));
+            builder.line_to(Point::new(MARGIN, bounds.height - MARGIN));
+            builder.line_to(Point::new(bounds.width - MARGIN, bounds.height - MARGIN));
+        });
+        frame.stroke(&axes, Stroke::default().with_color(palette.text));
+        let line = Path::new(|builder| {
+            builder.move_to(Point::new(x_pos(first), y_pos(0)));
+            for &(timestamp, count) in self.growth {
+                builder.line_to(Point::new(x_pos(timestamp), y_pos(count)));
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default()
+                .with_color(palette.primary)
+                .with_width(2.0),
+        );
+        frame.fill_text(Text {
+            content: format!("{} history items", self.growth.len()),
+            position: Point::new(MARGIN + 5.0, MARGIN),
+            color: palette.text,
+            ..Text::default()
+        });
+        vec![frame.into_geometry()]
+    }
+}
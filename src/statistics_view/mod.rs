@@ -0,0 +1,49 @@
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{
+            EntityColumnSearchParams, HistoryItemSearchParams, RelationshipSearchParams,
+        },
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+use self::statistics::Statistics;
+
+mod charts;
+pub(crate) mod statistics;
+pub(crate) mod widget;
+
+#[derive(Debug, Default)]
+pub(super) struct StatisticsViewState {
+    pub(super) display_protected: bool,
+    pub(super) statistics: Statistics,
+}
+
+impl StatisticsViewState {
+    pub(super) fn get_current_statistics(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Statistics, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(Statistics::default()),
+        };
+        let mut entity_columns: Vec<EntityColumn> =
+            db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+        let mut relationships = db.read_relationships(RelationshipSearchParams::new(None, None))?;
+        let history_items =
+            db.read_history_items(HistoryItemSearchParams::new(None, None, None, None))?;
+        if !self.display_protected {
+            entity_columns.retain(|col| !col.label.is_protected());
+            relationships.retain(|rel| !rel.role.is_protected());
+        }
+        Ok(Statistics::new(
+            &entity_columns,
+            &relationships,
+            &history_items,
+        ))
+    }
+}
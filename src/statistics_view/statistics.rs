@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use lorecore::{extractions::extract_labels, types::*};
+
+use crate::entity_view::category::CATEGORY_DESCRIPTOR;
+
+/// How many entries the lists of most used descriptors and roles show.
+const TOP_COUNT: usize = 10;
+
+/// Aggregated counts over the whole database, as shown on the dashboard.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Statistics {
+    pub(crate) entity_count: usize,
+    pub(crate) entities_per_category: Vec<(String, usize)>,
+    /// How many entities have a given number of descriptors.
+    pub(crate) descriptors_per_entity: Vec<(usize, usize)>,
+    pub(crate) most_used_descriptors: Vec<(String, usize)>,
+    pub(crate) most_used_roles: Vec<(String, usize)>,
+    pub(crate) history_items_per_century: Vec<(i32, usize)>,
+    pub(crate) total_words: usize,
+    pub(crate) average_words: f32,
+    /// The number of history items that existed at each creation timestamp, in order.
+    pub(crate) growth: Vec<(i64, usize)>,
+}
+
+impl Statistics {
+    pub(crate) fn new(
+        entity_columns: &[EntityColumn],
+        relationships: &[EntityRelationship],
+        history_items: &[HistoryItem],
+    ) -> Self {
+        let mut labels = extract_labels(entity_columns);
+        // Protected labels hold internal data such as eras and inverse roles.
+        labels.retain(|label| !label.is_protected());
+        let descriptions: Vec<&str> = entity_columns
+            .iter()
+            .filter(|col| !col.descriptor.is_protected())
+            .map(|col| col.description.to_str())
+            .collect();
+        let total_words: usize = descriptions
+            .iter()
+            .map(|d| d.split_whitespace().count())
+            .sum();
+        let average_words = if descriptions.is_empty() {
+            0.0
+        } else {
+            total_words as f32 / descriptions.len() as f32
+        };
+
+        Statistics {
+            entity_count: labels.len(),
+            entities_per_category: entities_per_category(entity_columns, &labels),
+            descriptors_per_entity: descriptors_per_entity(entity_columns, &labels),
+            most_used_descriptors: most_used(
+                entity_columns
+                    .iter()
+                    .filter(|col| !col.descriptor.is_protected())
+                    .map(|col| col.descriptor.to_str()),
+            ),
+            most_used_roles: most_used(relationships.iter().map(|rel| rel.role.to_str())),
+            history_items_per_century: history_items_per_century(history_items),
+            total_words,
+            average_words,
+            growth: growth(history_items),
+        }
+    }
+}
+
+fn entities_per_category(
+    entity_columns: &[EntityColumn],
+    labels: &[Label],
+) -> Vec<(String, usize)> {
+    let category_of: HashMap<&str, &str> = entity_columns
+        .iter()
+        .filter(|col| col.descriptor.to_str() == CATEGORY_DESCRIPTOR)
+        .map(|col| (col.label.to_str(), col.description.to_str()))
+        .collect();
+    let categories = labels.iter().map(|label| {
+        category_of
+            .get(label.to_str())
+            .copied()
+            .unwrap_or("[no category]")
+    });
+    let mut counts = count(categories);
+    counts.sort_by(|a, b| a.0.cmp(&b.0));
+    counts
+}
+
+fn descriptors_per_entity(
+    entity_columns: &[EntityColumn],
+    labels: &[Label],
+) -> Vec<(usize, usize)> {
+    let mut descriptor_counts: HashMap<&str, usize> = HashMap::new();
+    for col in entity_columns
+        .iter()
+        .filter(|col| !col.descriptor.is_protected())
+    {
+        *descriptor_counts.entry(col.label.to_str()).or_default() += 1;
+    }
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for label in labels {
+        let descriptor_count = descriptor_counts.get(label.to_str()).copied().unwrap_or(0);
+        *counts.entry(descriptor_count).or_default() += 1;
+    }
+    let mut counts: Vec<(usize, usize)> = counts.into_iter().collect();
+    counts.sort();
+    counts
+}
+
+fn history_items_per_century(history_items: &[HistoryItem]) -> Vec<(i32, usize)> {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for item in history_items {
+        *counts
+            .entry(item.year.to_int().div_euclid(100))
+            .or_default() += 1;
+    }
+    let mut counts: Vec<(i32, usize)> = counts.into_iter().collect();
+    counts.sort();
+    counts
+}
+
+fn growth(history_items: &[HistoryItem]) -> Vec<(i64, usize)> {
+    let mut timestamps: Vec<i64> = history_items
+        .iter()
+        .map(|item| item.timestamp.to_int())
+        .collect();
+    timestamps.sort();
+    timestamps
+        .into_iter()
+        .enumerate()
+        .map(|(i, timestamp)| (timestamp, i + 1))
+        .collect()
+}
+
+/// Occurrences of each distinct value, in no particular order.
+fn count<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(value, n)| (value.to_string(), n))
+        .collect()
+}
+
+fn most_used<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts = count(values);
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(TOP_COUNT);
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(label: &str, descriptor: &str, description: &str) -> EntityColumn {
+        EntityColumn {
+            label: label.into(),
+            descriptor: descriptor.into(),
+            description: description.into(),
+        }
+    }
+
+    #[test]
+    fn entities_are_counted_per_category_and_descriptor_count() {
+        let columns = vec![
+            column("frodo", CATEGORY_DESCRIPTOR, "hobbit"),
+            column("frodo", "Home", "The Shire"),
+            column("frodo", "Age", "50"),
+            column("sam", CATEGORY_DESCRIPTOR, "hobbit"),
+            column("sam", "Home", "The Shire too"),
+            column("ring", "Inscription", "One ring to rule them all"),
+        ];
+        let statistics = Statistics::new(&columns, &[], &[]);
+
+        assert_eq!(statistics.entity_count, 3);
+        assert_eq!(
+            statistics.entities_per_category,
+            vec![("[no category]".to_string(), 1), ("hobbit".to_string(), 2)]
+        );
+        assert_eq!(statistics.descriptors_per_entity, vec![(1, 2), (2, 1)]);
+        assert_eq!(statistics.most_used_descriptors[0], ("Home".to_string(), 2));
+        assert_eq!(statistics.total_words, 12);
+        assert_eq!(statistics.average_words, 3.0);
+    }
+}
@@ -0,0 +1,102 @@
+use iced::{
+    widget::{canvas::Canvas, Column, Row, Scrollable, Text},
+    Element, Length,
+};
+
+use crate::app::message_handling::GuiMessage;
+
+use super::{
+    charts::{BarChart, GrowthChart},
+    StatisticsViewState,
+};
+
+const GROWTH_CHART_HEIGHT: f32 = 200.0;
+
+pub(crate) fn new(state: &StatisticsViewState) -> Element<'_, GuiMessage> {
+    let statistics = &state.statistics;
+    let descriptors_per_entity = statistics
+        .descriptors_per_entity
+        .iter()
+        .map(|(descriptors, entities)| (format!("{} descriptors", descriptors), *entities))
+        .collect();
+    let history_items_per_century = statistics
+        .history_items_per_century
+        .iter()
+        .map(|(century, items)| {
+            (
+                format!("{} to {}", century * 100, century * 100 + 99),
+                *items,
+            )
+        })
+        .collect();
+    let col = Column::new()
+        .push(summary(state))
+        .push(
+            Row::new()
+                .push(chart(
+                    "Entities per category",
+                    statistics.entities_per_category.clone(),
+                ))
+                .push(chart("Descriptors per entity", descriptors_per_entity)),
+        )
+        .push(
+            Row::new()
+                .push(chart(
+                    "Most used descriptors",
+                    statistics.most_used_descriptors.clone(),
+                ))
+                .push(chart("Most used roles", statistics.most_used_roles.clone())),
+        )
+        .push(
+            Row::new()
+                .push(chart(
+                    "History items per century",
+                    history_items_per_century,
+                ))
+                .push(growth(state)),
+        )
+        .spacing(10)
+        .padding(5);
+    Scrollable::new(col)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+fn summary(state: &StatisticsViewState) -> Row<'_, GuiMessage> {
+    let statistics = &state.statistics;
+    Row::new()
+        .push(Text::new(format!("Entities: {}", statistics.entity_count)))
+        .push(Text::new(format!(
+            "Words in descriptions: {}",
+            statistics.total_words
+        )))
+        .push(Text::new(format!(
+            "Average words per description: {:.1}",
+            statistics.average_words
+        )))
+        .spacing(20)
+}
+
+fn chart<'a>(title: &'a str, bars: Vec<(String, usize)>) -> Column<'a, GuiMessage> {
+    let chart = BarChart { bars };
+    let height = chart.height();
+    Column::new()
+        .push(Text::new(title).size(20))
+        .push(Canvas::new(chart).width(Length::Fill).height(height))
+        .width(Length::FillPortion(1))
+}
+
+fn growth(state: &StatisticsViewState) -> Column<'_, GuiMessage> {
+    let chart = GrowthChart {
+        growth: &state.statistics.growth,
+    };
+    Column::new()
+        .push(Text::new("Growth over time").size(20))
+        .push(
+            Canvas::new(chart)
+                .width(Length::Fill)
+                .height(GROWTH_CHART_HEIGHT),
+        )
+        .width(Length::FillPortion(1))
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use lorecore::{
+    extractions::extract_labels,
+    sql::{lore_database::LoreDatabase, search_params::EntityColumnSearchParams},
+    types::*,
+};
+
+use crate::{
+    entity_view::category::{read_label_categories, CategoryFilter},
+    errors::LoreGuiError,
+};
+
+pub(crate) mod widget;
+
+#[derive(Debug, Default)]
+pub(super) struct TableViewState {
+    pub(super) display_protected: bool,
+    pub(super) edit_mode: bool,
+    pub(super) category_options: Vec<CategoryFilter>,
+    pub(super) category_filter: CategoryFilter,
+    pub(super) descriptor_options: Vec<Descriptor>,
+    pub(super) descriptors: Vec<Descriptor>,
+    pub(super) labels: Vec<Label>,
+    pub(super) entity_columns: Vec<EntityColumn>,
+    /// The descriptions of `entity_columns`, by label and then by descriptor.
+    pub(super) cells: HashMap<String, HashMap<String, Description>>,
+    pub(super) sort_column: Option<Descriptor>,
+    pub(super) sort_descending: bool,
+    pub(super) selected_cell: Option<(Label, Descriptor)>,
+    pub(super) cell_text: String,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum TableViewMessage {
+    CategoryFilterSelected(CategoryFilter),
+    DescriptorToggled(Descriptor, bool),
+    /// Sorts by label if no descriptor is given, and reverses when sorting by the same column.
+    SortBy(Option<Descriptor>),
+    CellSelected(Label, Descriptor),
+    CellTextUpdate(String),
+    CellSave,
+    ExportCsv,
+}
+
+impl TableViewState {
+    pub(super) fn get_current_entity_columns(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<EntityColumn>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let mut entity_columns =
+            db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+        if !self.display_protected {
+            entity_columns
+                .retain(|col| !col.label.is_protected() && !col.descriptor.is_protected());
+        }
+        Ok(entity_columns)
+    }
+
+    pub(super) fn get_cells(
+        entity_columns: &[EntityColumn],
+    ) -> HashMap<String, HashMap<String, Description>> {
+        let mut cells: HashMap<String, HashMap<String, Description>> = HashMap::new();
+        for col in entity_columns {
+            cells
+                .entry(col.label.to_str().to_string())
+                .or_default()
+                .insert(col.descriptor.to_str().to_string(), col.description.clone());
+        }
+        cells
+    }
+
+    pub(super) fn get_descriptor_options(entity_columns: &[EntityColumn]) -> Vec<Descriptor> {
+        let mut descriptors: Vec<Descriptor> = entity_columns
+            .iter()
+            .map(|col| col.descriptor.clone())
+            .collect();
+        descriptors.sort_by(|a, b| a.to_str().cmp(b.to_str()));
+        descriptors.dedup();
+        descriptors
+    }
+
+    /// The labels of the rows, filtered by category and in the chosen order.
+    pub(super) fn get_current_labels(
+        &self,
+        db: &Option<LoreDatabase>,
+    ) -> Result<Vec<Label>, LoreGuiError> {
+        let db = match db {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let label_categories = read_label_categories(db)?;
+        let mut labels = extract_labels(&self.entity_columns);
+        labels.retain(|label| self.category_filter.matches(label, &label_categories));
+        labels.sort_by(|a, b| match &self.sort_column {
+            Some(descriptor) => self
+                .get_cell_text(a, descriptor)
+                .cmp(&self.get_cell_text(b, descriptor))
+                .then_with(|| a.to_str().cmp(b.to_str())),
+            None => a.to_str().cmp(b.to_str()),
+        });
+        if self.sort_descending {
+            labels.reverse();
+        }
+        Ok(labels)
+    }
+
+    pub(super) fn get_cell_text(&self, label: &Label, descriptor: &Descriptor) -> &str {
+        self.cells
+            .get(label.to_str())
+            .and_then(|descriptions| descriptions.get(descriptor.to_str()))
+            .map(|description| description.to_str())
+            .unwrap_or("")
+    }
+
+    /// The table as it is displayed, with the descriptors as header row.
+    pub(super) fn to_rows(&self) -> Vec<Vec<String>> {
+        let header = std::iter::once("Label".to_string())
+            .chain(self.descriptors.iter().map(|d| d.to_str().to_string()))
+            .collect();
+        let rows = self.labels.iter().map(|label| {
+            std::iter::once(label.to_str().to_string())
+                .chain(
+                    self.descriptors
+                        .iter()
+                        .map(|descriptor| self.get_cell_text(label, descriptor).to_string()),
+                )
+                .collect()
+        });
+        std::iter::once(header).chain(rows).collect()
+    }
+}
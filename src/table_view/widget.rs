@@ -0,0 +1,158 @@
+use iced::{
+    widget::{
+        button,
+        scrollable::{Direction, Scrollbar},
+        Checkbox, Column, PickList, Row, Scrollable, Text, TextInput,
+    },
+    Alignment, Element, Length,
+};
+use lorecore::types::*;
+
+use crate::app::message_handling::GuiMessage;
+
+use super::{TableViewMessage, TableViewState};
+
+const CELL_WIDTH: f32 = 160.0;
+/// How many rows are shown at most, since every cell is a widget of its own.
+const MAX_ROWS: usize = 50;
+
+pub(crate) fn new(state: &TableViewState) -> Element<'_, GuiMessage> {
+    let mut col = Column::new().push(controls(state));
+    if state.edit_mode {
+        col = col.push(cell_editor(state));
+    }
+    if state.labels.len() > MAX_ROWS {
+        col = col.push(
+            Text::new(format!(
+                "Showing {} of {} entities. Choose a category to narrow them down.",
+                MAX_ROWS,
+                state.labels.len()
+            ))
+            .width(Length::Fill),
+        );
+    }
+    col = col.push(
+        Scrollable::new(table(state))
+            .direction(Direction::Both {
+                vertical: Scrollbar::default(),
+                horizontal: Scrollbar::default(),
+            })
+            .width(Length::Fill)
+            .height(Length::Fill),
+    );
+    Row::new()
+        .push(Scrollable::new(descriptor_selection(state)))
+        .push(col)
+        .into()
+}
+
+fn descriptor_selection(state: &TableViewState) -> Column<'_, GuiMessage> {
+    let mut col = Column::new().push(Text::new("Columns:"));
+    for descriptor in state.descriptor_options.iter() {
+        let descriptor = descriptor.clone();
+        let checked = state.descriptors.contains(&descriptor);
+        col = col.push(
+            Checkbox::new(checked)
+                .label(descriptor.to_str().to_string())
+                .on_toggle(move |checked| {
+                    GuiMessage::TableViewUpd(TableViewMessage::DescriptorToggled(
+                        descriptor.clone(),
+                        checked,
+                    ))
+                }),
+        );
+    }
+    col.spacing(5).padding(5)
+}
+
+fn controls(state: &TableViewState) -> Row<'_, GuiMessage> {
+    let category_filter = PickList::new(
+        state.category_options.clone(),
+        Some(state.category_filter.clone()),
+        |f| GuiMessage::TableViewUpd(TableViewMessage::CategoryFilterSelected(f)),
+    );
+    let export =
+        button("Export CSV").on_press(GuiMessage::TableViewUpd(TableViewMessage::ExportCsv));
+    Row::new()
+        .push(Text::new("Category:"))
+        .push(category_filter)
+        .push(export)
+        .align_y(Alignment::Center)
+        .spacing(5)
+        .padding(5)
+}
+
+fn cell_editor(state: &TableViewState) -> Row<'_, GuiMessage> {
+    let (label, descriptor) = match &state.selected_cell {
+        Some(cell) => cell,
+        None => {
+            return Row::new()
+                .push(Text::new("Select a cell to edit its description."))
+                .padding(5)
+        }
+    };
+    let input = TextInput::new("Description", &state.cell_text)
+        .on_input(|t| GuiMessage::TableViewUpd(TableViewMessage::CellTextUpdate(t)))
+        .on_submit(GuiMessage::TableViewUpd(TableViewMessage::CellSave));
+    let save = button("Save").on_press(GuiMessage::TableViewUpd(TableViewMessage::CellSave));
+    Row::new()
+        .push(Text::new(format!("{} / {}:", label, descriptor)))
+        .push(input)
+        .push(save)
+        .align_y(Alignment::Center)
+        .spacing(5)
+        .padding(5)
+}
+
+fn sort_header<'a>(
+    state: &TableViewState,
+    title: &'a str,
+    column: Option<Descriptor>,
+) -> Element<'a, GuiMessage> {
+    let title = if state.sort_column == column {
+        let arrow = if state.sort_descending { "v" } else { "^" };
+        format!("{} {}", title, arrow)
+    } else {
+        title.to_string()
+    };
+    button(Text::new(title))
+        .width(Length::Fixed(CELL_WIDTH))
+        .style(button::text)
+        .on_press(GuiMessage::TableViewUpd(TableViewMessage::SortBy(column)))
+        .into()
+}
+
+fn table(state: &TableViewState) -> Column<'_, GuiMessage> {
+    let mut header = Row::new().push(sort_header(state, "Label", None));
+    for descriptor in state.descriptors.iter() {
+        header = header.push(sort_header(
+            state,
+            descriptor.to_str(),
+            Some(descriptor.clone()),
+        ));
+    }
+    let mut col = Column::new().push(header.spacing(2));
+    for label in state.labels.iter().take(MAX_ROWS) {
+        let mut row = Row::new().push(Text::new(label.to_str()).width(Length::Fixed(CELL_WIDTH)));
+        for descriptor in state.descriptors.iter() {
+            let description = state.get_cell_text(label, descriptor);
+            let is_selected =
+                state.selected_cell.as_ref() == Some(&(label.clone(), descriptor.clone()));
+            let mut cell = button(Text::new(description)).width(Length::Fixed(CELL_WIDTH));
+            cell = if is_selected {
+                cell.style(button::primary)
+            } else {
+                cell.style(button::secondary)
+            };
+            if state.edit_mode {
+                cell = cell.on_press(GuiMessage::TableViewUpd(TableViewMessage::CellSelected(
+                    label.clone(),
+                    descriptor.clone(),
+                )));
+            }
+            row = row.push(cell);
+        }
+        col = col.push(row.spacing(2));
+    }
+    col.spacing(2).padding(5)
+}